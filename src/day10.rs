@@ -9,7 +9,7 @@ pub struct Point {
 fn parse(map: Vec<String>) -> Vec<Point> {
     map.iter()
         .enumerate()
-        .map(|(y, row)| {
+        .flat_map(|(y, row)| {
             row.chars()
                 .enumerate()
                 .filter_map(move |(x, position)| match position {
@@ -21,7 +21,6 @@ fn parse(map: Vec<String>) -> Vec<Point> {
                     _ => panic!("unexpected character {}", position),
                 })
         })
-        .flatten()
         .collect()
 }
 
@@ -50,14 +49,14 @@ fn is_seen(a: Point, b: Point, c: Point) -> bool {
     angle_differs || !same_side || is_closer
 }
 
-fn in_sight(asteroids: &Vec<Point>, a: Point, b: Point) -> bool {
+fn in_sight(asteroids: &[Point], a: Point, b: Point) -> bool {
     asteroids
         .iter()
         .filter(|&&c| c != a && c != b)
         .all(|c| is_seen(a, b, *c))
 }
 
-fn count_between_center(asteroids: &Vec<Point>, p: Point) -> usize {
+fn count_between_center(asteroids: &[Point], p: Point) -> usize {
     let center = Point { x: 0, y: 0 };
     asteroids
         .iter()
@@ -66,11 +65,11 @@ fn count_between_center(asteroids: &Vec<Point>, p: Point) -> usize {
         .count()
 }
 
-fn best_position(asteroids: &Vec<Point>) -> (Point, i64) {
+fn best_position(asteroids: &[Point]) -> (Point, i64) {
     let asteroids_in_sight = asteroids.iter().map(|&point| {
         let num_seen = asteroids
             .iter()
-            .filter(|&&other| other != point && in_sight(&asteroids, point, other))
+            .filter(|&&other| other != point && in_sight(asteroids, point, other))
             .count();
         (point, num_seen as i64)
     });
@@ -1,61 +1,33 @@
+use aoc_2019::intcode::{parse_program, Interpreter};
 
-fn run(mut ins: Vec<usize>) -> usize {
-    let mut insp = 0;
-    loop {
-        let opcode = ins[insp];
-        match opcode {
-            1 => {
-                let in1 = ins[insp + 1];
-                let in2 = ins[insp + 2];
-                let out = ins[insp + 3];
-                ins[out] = ins[in1] + ins[in2];
-            }
-            2 => {
-                let in1 = ins[insp +1];
-                let in2 = ins[insp + 2];
-                let out = ins[insp + 3];
-                ins[out] = ins[in1] * ins[in2];
-            }
-            99 => {
-                break;
-            }
-            _ => panic!("Unexpected opcode")
-        }
-        insp += 4;
-    }
-
-    ins[0]
+fn run(mut program: Interpreter) -> i64 {
+    program.run_to_halt().unwrap();
+    program.read_memory(0)
 }
 
-fn run_with(mut ins: Vec<usize>, noun: usize, verb: usize) -> usize {
-    ins[1] = noun;
-    ins[2] = verb;
-    run(ins)
+fn run_with(mut program: Interpreter, noun: i64, verb: i64) -> i64 {
+    program.write_memory(1, noun);
+    program.write_memory(2, verb);
+    run(program)
 }
 
-pub fn solve1(ins: Vec<String>) -> usize {
-    let ins: Vec<usize> = ins[0]
-        .split(',')
-        .map(|a| a.parse().unwrap())
-        .collect();
+pub fn solve1(ins: Vec<String>) -> i64 {
+    let program = Interpreter::new(&ins[0], &[]);
 
-    run_with(ins, 12, 2)
+    run_with(program, 12, 2)
 }
 
+pub fn solve2(ins: Vec<String>) -> i64 {
+    // Decoded once here, every try below runs a clone of this template.
+    let mut program = Interpreter::new(&ins[0], &[]);
+    program.set_instruction_cache(true);
 
-pub fn solve2(ins: Vec<String>) -> usize {
-    let ins: Vec<usize> = ins[0]
-    .split(',')
-    .map(|a| a.parse().unwrap())
-    .collect();
-
-    // let noun = 0;
-    // let mut verb = 0;
+    let len = parse_program(&ins[0]).unwrap().len() as i64;
     let expected = 19690720;
-    for noun in 0..ins.len() {
-        for verb in 0..ins.len() {
-            if run_with(ins.clone(), noun, verb) == expected {
-                return noun*100 + verb;
+    for noun in 0..len {
+        for verb in 0..len {
+            if run_with(program.clone(), noun, verb) == expected {
+                return noun * 100 + verb;
             }
         }
     }
@@ -69,25 +41,25 @@ mod tests {
 
     #[test]
     fn test1() {
-        let instructions = vec![1,0,0,0,99];
-        assert_eq!(run(instructions), 2);
+        let program = Interpreter::new("1,0,0,0,99", &[]);
+        assert_eq!(run(program), 2);
     }
 
     #[test]
     fn test1_2() {
-        let instructions = vec![2,3,0,3,99];
-        assert_eq!(run(instructions), 2);
+        let program = Interpreter::new("2,3,0,3,99", &[]);
+        assert_eq!(run(program), 2);
     }
 
     #[test]
     fn test1_3() {
-        let instructions = vec![2,4,4,5,99,0];
-        assert_eq!(run(instructions), 2);
+        let program = Interpreter::new("2,4,4,5,99,0", &[]);
+        assert_eq!(run(program), 2);
     }
 
     #[test]
     fn test1_4() {
-        let instructions = vec![1,1,1,4,99,5,6,0,99];
-        assert_eq!(run(instructions), 30);
+        let program = Interpreter::new("1,1,1,4,99,5,6,0,99", &[]);
+        assert_eq!(run(program), 30);
     }
 }
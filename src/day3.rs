@@ -30,7 +30,7 @@ impl FromStr for Step {
         };
 
         Ok(Step {
-            dir: dir,
+            dir,
             dist: dist.parse()?,
         })
     }
@@ -113,36 +113,28 @@ pub fn solve2(paths: Vec<String>) -> i32 {
                         for x_d in (x + 1)..=(x + step.dist) {
                             x = x_d;
                             distance += 1;
-                            if points.get(&(x, y)).is_none() {
-                                points.insert((x, y), distance);
-                            }
+                            points.entry((x, y)).or_insert(distance);
                         }
                     }
                     Dir::Left => {
                         for x_d in ((x - step.dist)..x).rev() {
                             x = x_d;
                             distance += 1;
-                            if points.get(&(x, y)).is_none() {
-                                points.insert((x, y), distance);
-                            }
+                            points.entry((x, y)).or_insert(distance);
                         }
                     }
                     Dir::Up => {
                         for y_d in (y + 1)..=(y + step.dist) {
                             y = y_d;
                             distance += 1;
-                            if points.get(&(x, y)).is_none() {
-                                points.insert((x, y), distance);
-                            }
+                            points.entry((x, y)).or_insert(distance);
                         }
                     }
                     Dir::Down => {
                         for y_d in ((y - step.dist)..y).rev() {
                             y = y_d;
                             distance += 1;
-                            if points.get(&(x, y)).is_none() {
-                                points.insert((x, y), distance);
-                            }
+                            points.entry((x, y)).or_insert(distance);
                         }
                     }
                 }
@@ -34,7 +34,7 @@ pub fn solve2() -> u64 {
             .zip(digits.chars().skip(1))
             .zip(digits.chars().skip(2))
             .zip(digits.chars().skip(3))
-            .any(|(((a, b), c), d)| (b == c && b != a && b != d));
+            .any(|(((a, b), c), d)| b == c && b != a && b != d);
 
         // Check adjacent digits at begin and end:
         let digits_bytes = digits.as_bytes();
@@ -1,7 +1,7 @@
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, VecDeque};
 
-fn parse(orbits: &Vec<String>) -> Vec<(&str, &str)> {
+fn parse(orbits: &[String]) -> Vec<(&str, &str)> {
     orbits
         .iter()
         .map(|orbit| {
@@ -1,131 +1,20 @@
+use aoc_2019::intcode::{Budgeted, Interpreter, RunOutput};
 use itertools::Itertools;
-use std::convert::TryInto;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Opcode {
-    number: i32,
-    mode1: i32,
-    mode2: i32,
-}
-
-impl Opcode {
-    fn new(v: i32) -> Opcode {
-        Opcode {
-            number: v % 100,
-            mode1: (v / 100) % 10,
-            mode2: (v / 1000) % 10,
-        }
-    }
-}
-
-fn read_param(ins: &Vec<i32>, value: i32, mode: i32) -> i32 {
-    if mode == 1 {
-        value
-    } else {
-        ins[value as usize]
-    }
-}
-
-enum RunOutput {
-    Halt,
-    Output(i32),
-}
-
-struct Interpreter {
-    insp: usize,
-    ins: Vec<i32>,
-    input: Vec<i32>,
-}
-
-impl Interpreter {
-    fn new(ins: Vec<i32>, input: &[i32]) -> Self {
-        Interpreter {
-            insp: 0,
-            ins: ins,
-            input: input.into(),
-        }
-    }
-
-    fn put_input(&mut self, input: i32) {
-        self.input.push(input);
-    }
-
-    fn run(&mut self) -> RunOutput {
-        loop {
-            let insp = self.insp;
-            let opcode = Opcode::new(self.ins[insp]);
-            match opcode.number {
-                1 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    let out = self.ins[insp + 3] as usize;
-                    self.ins[out] = in1 + in2;
-                    self.insp += 4;
-                }
-                2 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    let out = self.ins[insp + 3] as usize;
-                    self.ins[out] = in1 * in2;
-                    self.insp += 4;
-                }
-                3 => {
-                    let out = self.ins[insp + 1] as usize;
-                    self.ins[out] = self.input.remove(0);
-                    self.insp += 2;
-                }
-                4 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    self.insp += 2;
-                    return RunOutput::Output(in1);
-                }
-                5 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    if in1 != 0 {
-                        self.insp = in2.try_into().unwrap();
-                    } else {
-                        self.insp += 3;
-                    }
-                }
-                6 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    if in1 == 0 {
-                        self.insp = in2.try_into().unwrap();
-                    } else {
-                        self.insp += 3;
-                    }
-                }
-                7 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    let out = self.ins[insp + 3] as usize;
-                    self.ins[out] = if in1 < in2 { 1 } else { 0 };
-                    self.insp += 4;
-                }
-                8 => {
-                    let in1 = read_param(&self.ins, self.ins[insp + 1], opcode.mode1);
-                    let in2 = read_param(&self.ins, self.ins[insp + 2], opcode.mode2);
-                    let out: usize = self.ins[insp + 3].try_into().unwrap();
-                    self.ins[out] = if in1 == in2 { 1 } else { 0 };
-                    self.insp += 4;
-                }
-                99 => {
-                    return RunOutput::Halt;
-                }
-                _ => panic!("Unexpected opcode {:?}", opcode),
-            }
-        }
-    }
-}
+// Instructions an amplifier may execute before producing output, so a wrong
+// phase setting fails instead of looping forever.
+const STEP_BUDGET: u64 = 1_000_000;
 
-pub fn run_amplifiers_1(ins: Vec<i32>, phase_settings: Vec<i32>) -> i32 {
+pub fn run_amplifiers_1(program: &Interpreter, phase_settings: Vec<i64>) -> i64 {
     let mut input = 0;
 
-    for i in 0..5 {
-        let mut amplifier = Interpreter::new(ins.clone(), &[phase_settings[i], input]);
-        if let RunOutput::Output(out) = amplifier.run() {
+    for &phase_setting in phase_settings.iter() {
+        let mut amplifier = program.clone();
+        amplifier.put_input(phase_setting);
+        amplifier.put_input(input);
+        if let Budgeted::Done(RunOutput::Output(out)) =
+            amplifier.run_with_budget(STEP_BUDGET).unwrap()
+        {
             input = out;
         } else {
             panic!("Incorrect output!");
@@ -135,13 +24,17 @@ pub fn run_amplifiers_1(ins: Vec<i32>, phase_settings: Vec<i32>) -> i32 {
     input
 }
 
-pub fn run_amplifiers_2(ins: Vec<i32>, phase_settings: Vec<i32>) -> i32 {
+pub fn run_amplifiers_2(program: &Interpreter, phase_settings: Vec<i64>) -> i64 {
     let mut amplifiers = vec![];
     let mut input = 0;
 
-    for i in 0..5 {
-        let mut amplifier = Interpreter::new(ins.clone(), &[phase_settings[i], input]);
-        if let RunOutput::Output(out) = amplifier.run() {
+    for &phase_setting in phase_settings.iter() {
+        let mut amplifier = program.clone();
+        amplifier.put_input(phase_setting);
+        amplifier.put_input(input);
+        if let Budgeted::Done(RunOutput::Output(out)) =
+            amplifier.run_with_budget(STEP_BUDGET).unwrap()
+        {
             input = out;
         } else {
             panic!("Incorrect output!");
@@ -152,11 +45,14 @@ pub fn run_amplifiers_2(ins: Vec<i32>, phase_settings: Vec<i32>) -> i32 {
     'outer: loop {
         for amplifier in amplifiers.iter_mut() {
             amplifier.put_input(input);
-            match amplifier.run() {
-                RunOutput::Output(out) => {
+            match amplifier.run_with_budget(STEP_BUDGET).unwrap() {
+                Budgeted::Done(RunOutput::Output(out)) => {
                     input = out;
                 }
-                RunOutput::Halt => break 'outer,
+                Budgeted::Done(RunOutput::Halt) => break 'outer,
+                Budgeted::Done(RunOutput::NeedsInput) => panic!("Amplifier is waiting for input!"),
+                Budgeted::Done(RunOutput::Watch(_)) => unreachable!(),
+                Budgeted::Exhausted => panic!("Amplifier is stuck!"),
             }
         }
     }
@@ -164,13 +60,15 @@ pub fn run_amplifiers_2(ins: Vec<i32>, phase_settings: Vec<i32>) -> i32 {
     input
 }
 
-pub fn solve1(ins: Vec<String>) -> i32 {
-    let ins: Vec<i32> = ins[0].split(',').map(|a| a.parse().unwrap()).collect();
+pub fn solve1(ins: Vec<String>) -> i64 {
+    // Every permutation runs the same program, decode it only once.
+    let mut program = Interpreter::new(&ins[0], &[]);
+    program.set_instruction_cache(true);
 
     let mut max = 0;
     let mut max_phase_settings = None;
     for phase_settings in (0..=4).permutations(5) {
-        let signal = run_amplifiers_1(ins.clone(), phase_settings.clone());
+        let signal = run_amplifiers_1(&program, phase_settings.clone());
         if signal > max {
             max = signal;
             max_phase_settings = Some(phase_settings);
@@ -181,13 +79,15 @@ pub fn solve1(ins: Vec<String>) -> i32 {
     max
 }
 
-pub fn solve2(ins: Vec<String>) -> i32 {
-    let ins: Vec<i32> = ins[0].split(',').map(|a| a.parse().unwrap()).collect();
+pub fn solve2(ins: Vec<String>) -> i64 {
+    // Every permutation runs the same program, decode it only once.
+    let mut program = Interpreter::new(&ins[0], &[]);
+    program.set_instruction_cache(true);
 
     let mut max = 0;
     let mut max_phase_settings = None;
     for phase_settings in (5..=9).permutations(5) {
-        let signal = run_amplifiers_2(ins.clone(), phase_settings.clone());
+        let signal = run_amplifiers_2(&program, phase_settings.clone());
         if signal > max {
             max = signal;
             max_phase_settings = Some(phase_settings);
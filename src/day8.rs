@@ -42,7 +42,7 @@ pub fn solve2(input: Vec<String>) {
     // print image
     for (idx, pixel) in image.iter().enumerate() {
         if idx % width == 0 {
-            println!("");
+            println!();
         }
 
         let c = match pixel {
@@ -0,0 +1,1170 @@
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+mod arithmetic;
+mod ascii;
+mod asm;
+mod budget;
+mod cache;
+mod cfg;
+mod coverage;
+mod custom;
+mod debugger;
+mod decompile;
+mod device;
+mod disasm;
+#[cfg(test)]
+mod fixtures;
+pub mod generated;
+mod loops;
+mod memory;
+mod native;
+mod profile;
+mod session;
+mod snapshot;
+mod trace;
+mod transpile;
+mod undo;
+mod watch;
+mod word;
+
+pub use self::arithmetic::Arithmetic;
+pub use self::ascii::AsciiOutput;
+pub use self::asm::{assemble, assemble_cells, AssembleError};
+pub use self::budget::Budgeted;
+pub use self::cache::InstructionCache;
+pub use self::cfg::{BasicBlock, Cfg, Edge};
+pub use self::coverage::Coverage;
+pub use self::custom::{CustomOpcode, Param, RegisterError};
+pub use self::debugger::Debugger;
+pub use self::decompile::{decompile, decompile_cells};
+pub use self::device::{InputSource, OutputSink};
+pub use self::disasm::{decode, disassemble, disassemble_cells, Instruction, Line};
+pub use self::loops::LoopDetector;
+pub use self::memory::Memory;
+pub use self::native::{Compiled, Dispatch, Exit, Native};
+pub use self::profile::{AddressCount, BranchCount, Profile};
+pub use self::session::{Divergence, Event, Session};
+pub use self::trace::{TraceEntry, Tracer};
+pub use self::transpile::transpile;
+pub use self::undo::{UndoEntry, UndoLog};
+pub use self::watch::{Access, WatchHit, WatchKind, Watchpoint};
+pub use self::word::{BigInt, Word};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Opcode {
+    pub number: i64,
+    pub mode1: OpcodeMode,
+    pub mode2: OpcodeMode,
+    pub mode3: OpcodeMode,
+}
+
+impl Opcode {
+    // Returns None if any of the parameter modes is invalid.
+    pub fn new(v: i64) -> Option<Opcode> {
+        Some(Opcode {
+            number: v % 100,
+            mode1: OpcodeMode::new((v / 100) % 10)?,
+            mode2: OpcodeMode::new((v / 1000) % 10)?,
+            mode3: OpcodeMode::new((v / 10000) % 10)?,
+        })
+    }
+
+    pub fn mnemonic(&self) -> Option<&'static str> {
+        match self.number {
+            1 => Some("add"),
+            2 => Some("mul"),
+            3 => Some("in"),
+            4 => Some("out"),
+            5 => Some("jnz"),
+            6 => Some("jz"),
+            7 => Some("lt"),
+            8 => Some("eq"),
+            9 => Some("arb"),
+            99 => Some("hlt"),
+            _ => None,
+        }
+    }
+
+    pub fn param_count(&self) -> Option<usize> {
+        match self.number {
+            1 | 2 | 7 | 8 => Some(3),
+            5 | 6 => Some(2),
+            3 | 4 | 9 => Some(1),
+            99 => Some(0),
+            _ => None,
+        }
+    }
+
+    // Index of the parameter the instruction writes to, if any.
+    pub fn write_param(&self) -> Option<usize> {
+        match self.number {
+            1 | 2 | 7 | 8 => Some(2),
+            3 => Some(0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OpcodeMode {
+    Positional,
+    Immediate,
+    Relative,
+}
+
+impl OpcodeMode {
+    pub fn new(v: i64) -> Option<Self> {
+        match v {
+            0 => Some(OpcodeMode::Positional),
+            1 => Some(OpcodeMode::Immediate),
+            2 => Some(OpcodeMode::Relative),
+            _ => None,
+        }
+    }
+
+    // Digit used to encode the mode in an opcode.
+    pub fn digit(&self) -> i64 {
+        match self {
+            OpcodeMode::Positional => 0,
+            OpcodeMode::Immediate => 1,
+            OpcodeMode::Relative => 2,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RunOutput<W = i64> {
+    Halt,
+    Output(W),
+    // Paused on an input instruction with an empty input queue. Running
+    // again after put_input resumes from the same instruction.
+    NeedsInput,
+    // Paused after an instruction accessed a watched address.
+    Watch(WatchHit<W>),
+}
+
+// Errors carry the instruction pointer and the raw opcode of the failing
+// instruction, except for parse errors.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IntcodeError {
+    // Program text has a cell that isn't a number fitting the word type. An
+    // empty program fails at cell 0.
+    InvalidProgram {
+        index: usize,
+    },
+    UnknownOpcode {
+        insp: usize,
+        opcode: i64,
+    },
+    InvalidMode {
+        insp: usize,
+        opcode: i64,
+    },
+    // Write parameter was given in immediate mode.
+    ImmediateWrite {
+        insp: usize,
+        opcode: i64,
+        operand: i64,
+    },
+    // Parameter or jump target resolved to a negative address or one past the memory limit.
+    InvalidAddress {
+        insp: usize,
+        opcode: i64,
+        address: i64,
+    },
+    // Program needed input while being run to halt.
+    NoInput {
+        insp: usize,
+        opcode: i64,
+    },
+    // Backward jump returned to an earlier state without I/O in between. The
+    // loop runs in start..end.
+    InfiniteLoop {
+        insp: usize,
+        opcode: i64,
+        start: usize,
+        end: usize,
+    },
+    // Add or multiply overflowed with Arithmetic::Checked, or an adjustment
+    // of the relative base overflowed.
+    Overflow {
+        insp: usize,
+        opcode: i64,
+        left: i64,
+        right: i64,
+    },
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IntcodeError::InvalidProgram { index } => {
+                write!(f, "invalid value in cell {} of program", index)
+            }
+            IntcodeError::UnknownOpcode { insp, opcode } => {
+                write!(f, "unknown opcode {} at {}", opcode, insp)
+            }
+            IntcodeError::InvalidMode { insp, opcode } => {
+                write!(f, "invalid parameter mode in opcode {} at {}", opcode, insp)
+            }
+            IntcodeError::ImmediateWrite {
+                insp,
+                opcode,
+                operand,
+            } => write!(
+                f,
+                "immediate mode write of operand {} by opcode {} at {}",
+                operand, opcode, insp
+            ),
+            IntcodeError::InvalidAddress {
+                insp,
+                opcode,
+                address,
+            } => write!(
+                f,
+                "invalid address {} used by opcode {} at {}",
+                address, opcode, insp
+            ),
+            IntcodeError::NoInput { insp, opcode } => {
+                write!(f, "no input available for opcode {} at {}", opcode, insp)
+            }
+            IntcodeError::InfiniteLoop {
+                insp,
+                opcode,
+                start,
+                end,
+            } => write!(
+                f,
+                "infinite loop in {}..{} detected by opcode {} at {}",
+                start, end, opcode, insp
+            ),
+            IntcodeError::Overflow {
+                insp,
+                opcode,
+                left,
+                right,
+            } => write!(
+                f,
+                "overflow of {} and {} in opcode {} at {}",
+                left, right, opcode, insp
+            ),
+        }
+    }
+}
+
+impl Error for IntcodeError {}
+
+// Parse comma separated program into memory cells.
+pub fn parse_program(program: &str) -> Result<Vec<i64>, IntcodeError> {
+    parse_words(program)
+}
+
+// Cells of a comma separated program, an error if a value isn't a valid W.
+pub fn parse_words<W: Word>(program: &str) -> Result<Vec<W>, IntcodeError> {
+    program
+        .trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, a)| {
+            a.trim()
+                .parse()
+                .map_err(|_| IntcodeError::InvalidProgram { index })
+        })
+        .collect()
+}
+
+// Intcode VM with memory cells of type W, i64 unless a program needs i32,
+// i128 or BigInt cells. Values in errors are clamped to i64. Snapshots, the
+// debugger, compiled code and ASCII helpers are only available for i64.
+pub struct Interpreter<W: Word = i64> {
+    insp: usize,
+    memory: Memory<W>,
+    input: VecDeque<W>,
+    relative_base: W,
+    memory_limit: Option<usize>,
+    arithmetic: Arithmetic,
+    input_source: Option<Box<dyn InputSource<W>>>,
+    output_sink: Option<Box<dyn OutputSink<W>>>,
+    tracer: Option<Tracer>,
+    recording: Option<Vec<Event<W>>>,
+    undo_log: Option<UndoLog<W>>,
+    watchpoints: Vec<Watchpoint>,
+    profile: Option<Profile>,
+    coverage: Option<Coverage>,
+    cache: Option<InstructionCache>,
+    loop_detector: Option<LoopDetector<W>>,
+    custom_opcodes: HashMap<i64, CustomOpcode<W>>,
+}
+
+// Attached devices and the tracer are not cloned, the clone starts without them.
+impl<W: Word> Clone for Interpreter<W> {
+    fn clone(&self) -> Self {
+        Interpreter {
+            insp: self.insp,
+            memory: self.memory.clone(),
+            input: self.input.clone(),
+            relative_base: self.relative_base.clone(),
+            memory_limit: self.memory_limit,
+            arithmetic: self.arithmetic,
+            input_source: None,
+            output_sink: None,
+            tracer: None,
+            recording: self.recording.clone(),
+            undo_log: self.undo_log.clone(),
+            watchpoints: self.watchpoints.clone(),
+            profile: self.profile.clone(),
+            coverage: self.coverage.clone(),
+            cache: self.cache.clone(),
+            loop_detector: self.loop_detector.clone(),
+            custom_opcodes: self.custom_opcodes.clone(),
+        }
+    }
+}
+
+impl<W: Word> fmt::Debug for Interpreter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Interpreter")
+            .field("insp", &self.insp)
+            .field("memory", &self.memory)
+            .field("input", &self.input)
+            .field("relative_base", &self.relative_base)
+            .field("memory_limit", &self.memory_limit)
+            .field("arithmetic", &self.arithmetic)
+            .field("input_source", &self.input_source.is_some())
+            .field("output_sink", &self.output_sink.is_some())
+            .field("tracer", &self.tracer.is_some())
+            .field("recording", &self.recording)
+            .field("undo_log", &self.undo_log)
+            .field("watchpoints", &self.watchpoints)
+            .field("profile", &self.profile.is_some())
+            .field("coverage", &self.coverage.is_some())
+            .field("cache", &self.cache.is_some())
+            .field("loop_detector", &self.loop_detector.is_some())
+            .field("custom_opcodes", &self.custom_opcodes)
+            .finish()
+    }
+}
+
+impl Interpreter {
+    // Panics if the program doesn't parse, see parse.
+    pub fn new(program: &str, input: &[i64]) -> Self {
+        Interpreter::parse(program, input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn parse(program: &str, input: &[i64]) -> Result<Self, IntcodeError> {
+        Ok(Interpreter::from_memory(
+            Memory::new(parse_program(program)?),
+            input,
+        ))
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    pub fn from_memory(memory: Memory<W>, input: &[W]) -> Self {
+        Interpreter {
+            insp: 0,
+            memory,
+            input: input.iter().cloned().collect(),
+            relative_base: W::default(),
+            memory_limit: None,
+            arithmetic: Arithmetic::default(),
+            input_source: None,
+            output_sink: None,
+            tracer: None,
+            recording: None,
+            undo_log: None,
+            watchpoints: vec![],
+            profile: None,
+            coverage: None,
+            cache: None,
+            loop_detector: None,
+            custom_opcodes: HashMap::new(),
+        }
+    }
+
+    // Read input from the source after the input queue runs out.
+    pub fn attach_input<I: InputSource<W> + 'static>(&mut self, source: I) {
+        self.input_source = Some(Box::new(source));
+    }
+
+    // Send all output to the sink instead of returning it from run.
+    pub fn attach_output<O: OutputSink<W> + 'static>(&mut self, sink: O) {
+        self.output_sink = Some(Box::new(sink));
+    }
+
+    // Record every executed instruction to the writer as JSON lines.
+    pub fn start_trace<T: Write + 'static>(&mut self, writer: T) {
+        self.tracer = Some(Tracer::new(writer));
+    }
+
+    // Stop tracing and flush the trace.
+    pub fn finish_trace(&mut self) -> io::Result<()> {
+        match self.tracer.take() {
+            Some(tracer) => tracer.finish(),
+            None => Ok(()),
+        }
+    }
+
+    // Record input and output values from now on, see Session.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(vec![]);
+    }
+
+    // Stop recording and return the recorded session.
+    pub fn take_recording(&mut self) -> Option<Session<W>> {
+        self.recording.take().map(|events| Session { events })
+    }
+
+    fn record(&mut self, event: Event<W>) {
+        if let Some(events) = self.recording.as_mut() {
+            if event != Event::Halt || events.last() != Some(&Event::Halt) {
+                events.push(event);
+            }
+        }
+    }
+
+    // Next input value without consuming it, also used by generated code. The
+    // input source is asked when the queue is empty.
+    fn peek_input(&mut self) -> Option<W> {
+        if self.input.is_empty() {
+            let value = self.input_source.as_mut().and_then(|s| s.next_input())?;
+            self.input.push_back(value);
+        }
+        self.input.front().cloned()
+    }
+
+    fn consume_input(&mut self) {
+        if let Some(value) = self.input.pop_front() {
+            self.record(Event::Input(value));
+        }
+    }
+
+    // Pass output to the sink. Returns the value back if there is no sink, it
+    // then has to be returned from run.
+    fn send(&mut self, value: W) -> Option<W> {
+        self.record(Event::Output(value.clone()));
+        match self.output_sink.as_mut() {
+            Some(sink) => {
+                sink.output(value);
+                None
+            }
+            None => Some(value),
+        }
+    }
+
+    fn halt(&mut self) {
+        self.record(Event::Halt);
+    }
+
+    // Limit addressable memory to `limit` cells. Accesses at or past the
+    // limit fail with IntcodeError::InvalidAddress.
+    pub fn set_memory_limit(&mut self, limit: Option<usize>) {
+        self.memory_limit = limit;
+    }
+
+    pub fn put_input(&mut self, input: W) {
+        self.input.push_back(input);
+    }
+
+    pub fn read_memory(&self, address: usize) -> W {
+        self.memory.read(address)
+    }
+
+    pub fn write_memory(&mut self, address: usize, value: W) {
+        self.store(address, value);
+    }
+
+    pub fn memory(&self) -> &Memory<W> {
+        &self.memory
+    }
+
+    pub fn insp(&self) -> usize {
+        self.insp
+    }
+
+    pub fn relative_base(&self) -> W {
+        self.relative_base.clone()
+    }
+
+    // Input values queued with put_input that have not been read yet.
+    pub fn pending_input(&self) -> &VecDeque<W> {
+        &self.input
+    }
+
+    // Decode the instruction at the address like disasm::decode, including
+    // registered opcodes. None as well if a parameter doesn't fit in an i64.
+    pub fn instruction_at(&self, address: usize) -> Option<Instruction> {
+        let (opcode, params, write_param) = self.decode_at(address)?;
+        let modes = [opcode.mode1, opcode.mode2, opcode.mode3];
+        if write_param.is_some_and(|i| modes[i] == OpcodeMode::Immediate) {
+            return None;
+        }
+        Some(Instruction {
+            address,
+            opcode,
+            params: params.iter().map(Word::to_i64).collect::<Option<_>>()?,
+        })
+    }
+
+    // Raw opcode at insp, for errors.
+    fn opcode(&self) -> i64 {
+        self.read_memory(self.insp).saturate()
+    }
+
+    fn invalid_address(&self, address: &W) -> IntcodeError {
+        IntcodeError::InvalidAddress {
+            insp: self.insp,
+            opcode: self.opcode(),
+            address: address.saturate(),
+        }
+    }
+
+    #[inline(always)]
+    fn check_address(&self, address: &W) -> Result<usize, IntcodeError> {
+        match address.to_i64().and_then(|a| a.try_into().ok()) {
+            Some(checked) if self.memory_limit.is_none_or(|limit| checked < limit) => Ok(checked),
+            _ => Err(self.invalid_address(address)),
+        }
+    }
+
+    // Relative base plus offset. A sum that doesn't fit in a word is an
+    // invalid address, whatever the arithmetic policy.
+    fn relative_address(&self, offset: &W) -> Result<W, IntcodeError> {
+        self.relative_base
+            .checked_add(offset)
+            .ok_or_else(|| self.invalid_address(&self.relative_base.saturating_add(offset)))
+    }
+
+    // Resolve the memory address of a positional or relative parameter.
+    #[inline(always)]
+    fn param_address(&self, param: &W, mode: OpcodeMode) -> Result<usize, IntcodeError> {
+        match mode {
+            OpcodeMode::Relative => self.check_address(&self.relative_address(param)?),
+            _ => self.check_address(param),
+        }
+    }
+
+    #[inline(always)]
+    fn read_param(&self, param: &W, mode: OpcodeMode) -> Result<W, IntcodeError> {
+        match mode {
+            OpcodeMode::Immediate => Ok(param.clone()),
+            _ => Ok(self.read_memory(self.param_address(param, mode)?)),
+        }
+    }
+
+    #[inline(always)]
+    fn write_param(&mut self, param: &W, mode: OpcodeMode, value: W) -> Result<(), IntcodeError> {
+        if mode == OpcodeMode::Immediate {
+            return Err(IntcodeError::ImmediateWrite {
+                insp: self.insp,
+                opcode: self.opcode(),
+                operand: param.saturate(),
+            });
+        }
+
+        let address = self.param_address(param, mode)?;
+        self.store(address, value);
+        Ok(())
+    }
+
+    // Value of the parameter at insp + offset.
+    #[inline(always)]
+    fn operand(&self, offset: usize, mode: OpcodeMode) -> Result<W, IntcodeError> {
+        self.read_param(&self.read_memory(self.insp + offset), mode)
+    }
+
+    #[inline(always)]
+    fn write_operand(
+        &mut self,
+        offset: usize,
+        mode: OpcodeMode,
+        value: W,
+    ) -> Result<(), IntcodeError> {
+        let param = self.read_memory(self.insp + offset);
+        self.write_param(&param, mode, value)
+    }
+
+    fn jump_target(&self, target: &W) -> Result<usize, IntcodeError> {
+        self.check_address(target)
+    }
+
+    // Opcode, parameters and index of the written parameter of the
+    // instruction at the address. None for unknown opcodes and invalid modes.
+    fn decode_at(&self, address: usize) -> Option<(Opcode, Vec<W>, Option<usize>)> {
+        let opcode = self.read_memory(address).to_i64().and_then(Opcode::new)?;
+        let (count, write_param) = self.signature(opcode)?;
+        let params = (1..=count)
+            .map(|i| Some(self.read_memory(address.checked_add(i)?)))
+            .collect::<Option<_>>()?;
+        Some((opcode, params, write_param))
+    }
+
+    // Addresses read by positional and relative parameters of the instruction
+    // at insp.
+    fn read_addresses(&self) -> Vec<usize> {
+        let (opcode, params, write_param) = match self.decode_at(self.insp) {
+            Some(decoded) => decoded,
+            None => return vec![],
+        };
+        let modes = [opcode.mode1, opcode.mode2, opcode.mode3];
+        params
+            .iter()
+            .zip(modes.iter())
+            .enumerate()
+            .filter(|&(i, (_, &mode))| Some(i) != write_param && mode != OpcodeMode::Immediate)
+            .filter_map(|(_, (param, &mode))| self.param_address(param, mode).ok())
+            .collect()
+    }
+
+    // Trace entry for the instruction at insp, without the written value.
+    // Returns None if the instruction is going to fail.
+    fn trace_entry(&self) -> Option<TraceEntry<W>> {
+        let (opcode, params, write_param) = self.decode_at(self.insp)?;
+        let modes = [opcode.mode1, opcode.mode2, opcode.mode3];
+        let mut entry = TraceEntry {
+            insp: self.insp,
+            opcode: self.opcode(),
+            modes: modes[..params.len()]
+                .iter()
+                .map(|mode| mode.digit())
+                .collect(),
+            operands: vec![],
+            write: None,
+            relative_base: self.relative_base.clone(),
+        };
+        for (i, (param, &mode)) in params.iter().zip(modes.iter()).enumerate() {
+            if Some(i) == write_param {
+                entry.write = Some((self.param_address(param, mode).ok()?, W::default()));
+            } else {
+                entry.operands.push(self.read_param(param, mode).ok()?);
+            }
+        }
+        Some(entry)
+    }
+
+    // Step with tracing, undo log, watchpoints, profiling, coverage or loop
+    // detection enabled.
+    fn instrumented_step(&mut self) -> Result<Option<RunOutput<W>>, IntcodeError> {
+        let mut entry = match self.trace_entry() {
+            Some(entry) => entry,
+            None => return self.execute(),
+        };
+        let undo = UndoEntry {
+            insp: self.insp,
+            relative_base: self.relative_base.clone(),
+            write: entry
+                .write
+                .as_ref()
+                .map(|&(address, _)| (address, self.read_memory(address))),
+            input: entry.opcode % 100 == 3,
+        };
+        let instruction = match self.coverage {
+            Some(_) => self.instruction_at(self.insp),
+            None => None,
+        };
+        // Read addresses with their values before execution.
+        let reads: Vec<(usize, W)> = if self.watchpoints.is_empty() && self.coverage.is_none() {
+            vec![]
+        } else {
+            self.read_addresses()
+                .into_iter()
+                .map(|address| (address, self.read_memory(address)))
+                .collect()
+        };
+
+        let result = self.execute();
+        let output = match result {
+            Ok(Some(RunOutput::NeedsInput)) | Err(_) => return result,
+            Ok(output) => output,
+        };
+        if let Some(tracer) = self.tracer.as_mut() {
+            let memory = &self.memory;
+            entry.write = entry
+                .write
+                .map(|(address, _)| (address, memory.read(address)));
+            tracer.record(&entry);
+        }
+        if let Some(undo_log) = self.undo_log.as_mut() {
+            if output != Some(RunOutput::Halt) {
+                undo_log.push(undo.clone());
+            }
+        }
+        if let Some(profile) = self.profile.as_mut() {
+            // Decided by the condition, a taken jump may still land on the
+            // next instruction.
+            let taken = match entry.opcode % 100 {
+                5 => Some(!entry.operands[0].is_zero()),
+                6 => Some(entry.operands[0].is_zero()),
+                _ => None,
+            };
+            profile.record(undo.insp, entry.opcode, taken);
+        }
+        if let (Some(coverage), Some(instruction)) = (self.coverage.as_mut(), instruction) {
+            let addresses: Vec<usize> = reads.iter().map(|&(address, _)| address).collect();
+            coverage.record(
+                instruction,
+                &addresses,
+                undo.write.as_ref().map(|&(address, _)| address),
+            );
+        }
+        self.check_loop(undo.insp, entry.opcode)?;
+
+        let written = undo
+            .write
+            .clone()
+            .filter(|&(address, _)| self.is_watched(address, Access::Write))
+            .map(|(address, old)| (Access::Write, address, old, self.read_memory(address)));
+        let read = reads
+            .iter()
+            .find(|&&(address, _)| self.is_watched(address, Access::Read))
+            .map(|(address, value)| (Access::Read, *address, value.clone(), value.clone()));
+        if let Some((access, address, old, new)) = written.or(read) {
+            return Ok(Some(RunOutput::Watch(WatchHit {
+                insp: entry.insp,
+                opcode: entry.opcode,
+                access,
+                address,
+                old,
+                new,
+                output: match output {
+                    Some(RunOutput::Output(value)) => Some(value),
+                    _ => None,
+                },
+            })));
+        }
+        Ok(output)
+    }
+
+    // Execute a single instruction. Returns Some when the program produced output,
+    // halted or needs input.
+    pub fn step(&mut self) -> Result<Option<RunOutput<W>>, IntcodeError> {
+        if self.is_instrumented() {
+            return self.instrumented_step();
+        }
+        self.execute()
+    }
+
+    // Whether any instrumentation needs instrumented_step. It can't change
+    // while running, so run checks it once.
+    fn is_instrumented(&self) -> bool {
+        self.tracer.is_some()
+            || self.undo_log.is_some()
+            || !self.watchpoints.is_empty()
+            || self.profile.is_some()
+            || self.coverage.is_some()
+            || self.loop_detector.is_some()
+    }
+
+    // Everything on the path of an instruction is inline(always), the
+    // compiler leaves most of it out of line otherwise and run gets slower.
+    #[inline(always)]
+    fn execute(&mut self) -> Result<Option<RunOutput<W>>, IntcodeError> {
+        let opcode = self.fetch()?;
+        match opcode.number {
+            // Addition
+            1 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                let sum = self.add(&in1, &in2)?;
+                self.write_operand(3, opcode.mode3, sum)?;
+                self.insp += 4;
+            }
+            // Product
+            2 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                let product = self.mul(&in1, &in2)?;
+                self.write_operand(3, opcode.mode3, product)?;
+                self.insp += 4;
+            }
+            // Input
+            3 => {
+                let input_value = match self.peek_input() {
+                    Some(value) => value,
+                    None => return Ok(Some(RunOutput::NeedsInput)),
+                };
+                self.write_operand(1, opcode.mode1, input_value)?;
+                self.consume_input();
+                self.insp += 2;
+            }
+            // Output
+            4 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                self.insp += 2;
+                if let Some(value) = self.send(in1) {
+                    return Ok(Some(RunOutput::Output(value)));
+                }
+            }
+            // Jump if true
+            5 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                if !in1.is_zero() {
+                    self.insp = self.jump_target(&in2)?;
+                } else {
+                    self.insp += 3;
+                }
+            }
+            // Jump if false
+            6 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                if in1.is_zero() {
+                    self.insp = self.jump_target(&in2)?;
+                } else {
+                    self.insp += 3;
+                }
+            }
+            // Less than
+            7 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                let is_less = W::from_bool(in1 < in2);
+                self.write_operand(3, opcode.mode3, is_less)?;
+                self.insp += 4;
+            }
+            // Equals
+            8 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                let in2 = self.operand(2, opcode.mode2)?;
+                let is_equal = W::from_bool(in1 == in2);
+                self.write_operand(3, opcode.mode3, is_equal)?;
+                self.insp += 4;
+            }
+            // Adjust relative base
+            9 => {
+                let in1 = self.operand(1, opcode.mode1)?;
+                self.adjust_relative_base(&in1)?;
+                self.insp += 2;
+            }
+            99 => {
+                self.halt();
+                return Ok(Some(RunOutput::Halt));
+            }
+            _ => {
+                if !self.execute_custom(opcode)? {
+                    return Err(IntcodeError::UnknownOpcode {
+                        insp: self.insp,
+                        opcode: self.opcode(),
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    // Run until the program outputs a value or halts.
+    pub fn run(&mut self) -> Result<RunOutput<W>, IntcodeError> {
+        if self.is_instrumented() {
+            loop {
+                if let Some(output) = self.instrumented_step()? {
+                    return Ok(output);
+                }
+            }
+        }
+        loop {
+            if let Some(output) = self.execute()? {
+                return Ok(output);
+            }
+        }
+    }
+
+    pub fn run_to_halt(&mut self) -> Result<Vec<W>, IntcodeError> {
+        let mut output = vec![];
+        loop {
+            match self.run()? {
+                RunOutput::Output(out) => output.push(out),
+                RunOutput::Halt => return Ok(output),
+                RunOutput::Watch(hit) => output.extend(hit.output),
+                RunOutput::NeedsInput => {
+                    return Err(IntcodeError::NoInput {
+                        insp: self.insp,
+                        opcode: self.opcode(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_opcode() {
+        assert_eq!(
+            Opcode::new(99),
+            Some(Opcode {
+                number: 99,
+                mode1: OpcodeMode::Positional,
+                mode2: OpcodeMode::Positional,
+                mode3: OpcodeMode::Positional,
+            })
+        );
+        assert_eq!(
+            Opcode::new(21108),
+            Some(Opcode {
+                number: 8,
+                mode1: OpcodeMode::Immediate,
+                mode2: OpcodeMode::Immediate,
+                mode3: OpcodeMode::Relative,
+            })
+        );
+        assert_eq!(Opcode::new(301), None);
+    }
+
+    #[test]
+    fn test_add_and_multiply() {
+        let mut t = Interpreter::new("1,9,10,3,2,3,11,0,99,30,40,50", &[]);
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+        assert_eq!(t.read_memory(0), 3500);
+    }
+
+    #[test]
+    fn test_input_output() {
+        let mut t = Interpreter::new("3,0,4,0,99", &[-42]);
+        assert_eq!(t.run_to_halt(), Ok(vec![-42]));
+    }
+
+    #[test]
+    fn test_jumps() {
+        let program = "3,3,1105,-1,9,1101,0,0,12,4,12,99,1";
+        assert_eq!(Interpreter::new(program, &[0]).run_to_halt(), Ok(vec![0]));
+        assert_eq!(Interpreter::new(program, &[7]).run_to_halt(), Ok(vec![1]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut t = Interpreter::new("1,0,0,0,42", &[]);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::UnknownOpcode {
+                insp: 4,
+                opcode: 42
+            })
+        );
+
+        let mut t = Interpreter::new("301,0,0,0,99", &[]);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::InvalidMode {
+                insp: 0,
+                opcode: 301
+            })
+        );
+
+        let mut t = Interpreter::new("11101,1,1,5,99", &[]);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::ImmediateWrite {
+                insp: 0,
+                opcode: 11101,
+                operand: 5
+            })
+        );
+
+        let mut t = Interpreter::new("4,-3,99", &[]);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::InvalidAddress {
+                insp: 0,
+                opcode: 4,
+                address: -3
+            })
+        );
+
+        let mut t = Interpreter::new("3,0,99", &[]);
+        assert_eq!(
+            t.run_to_halt(),
+            Err(IntcodeError::NoInput { insp: 0, opcode: 3 })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Interpreter::parse("", &[]).unwrap_err(),
+            IntcodeError::InvalidProgram { index: 0 }
+        );
+        assert_eq!(
+            Interpreter::parse("1,0,x,0,99", &[])
+                .unwrap_err()
+                .to_string(),
+            "invalid value in cell 2 of program"
+        );
+        assert_eq!(
+            parse_program("99,"),
+            Err(IntcodeError::InvalidProgram { index: 1 })
+        );
+        assert!(Interpreter::parse(" 104,7,99\n", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let program = "1101,1,1,20,4,20,99";
+        assert_eq!(Interpreter::new(program, &[]).run_to_halt(), Ok(vec![2]));
+
+        let mut t = Interpreter::new(program, &[]);
+        t.set_memory_limit(Some(20));
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::InvalidAddress {
+                insp: 0,
+                opcode: 1101,
+                address: 20
+            })
+        );
+    }
+
+    #[test]
+    fn test_needs_input() {
+        let mut t = Interpreter::new("3,20,3,21,1,20,21,22,4,22,99", &[]);
+        assert_eq!(t.run(), Ok(RunOutput::NeedsInput));
+        assert_eq!(t.run(), Ok(RunOutput::NeedsInput));
+        t.put_input(3);
+        assert_eq!(t.run(), Ok(RunOutput::NeedsInput));
+        t.put_input(4);
+        assert_eq!(t.run(), Ok(RunOutput::Output(7)));
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+    }
+
+    #[test]
+    fn test_devices() {
+        let program = "3,20,3,21,1,20,21,22,4,22,4,20,99";
+        let mut inputs = vec![2, 5].into_iter();
+        let output = Rc::new(RefCell::new(vec![]));
+        let sink = output.clone();
+
+        let mut t = Interpreter::new(program, &[]);
+        t.attach_input(move || inputs.next());
+        t.attach_output(move |value| sink.borrow_mut().push(value));
+        assert_eq!(t.run_to_halt(), Ok(vec![]));
+        assert_eq!(*output.borrow(), vec![7, 2]);
+    }
+
+    #[derive(Clone)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Squares its input twice and outputs the result.
+    const SQUARE_TWICE: &str = "3,13,2,13,13,13,2,13,13,13,4,13,99,0";
+
+    fn with_words<W: Word>(program: &str, input: &[W]) -> Interpreter<W> {
+        Interpreter::from_memory(Memory::new(parse_words(program).unwrap()), input)
+    }
+
+    #[test]
+    fn test_word_sizes() {
+        let mut t = with_words::<i32>(SQUARE_TWICE, &[1000]);
+        assert_eq!(
+            t.run_to_halt(),
+            Err(IntcodeError::Overflow {
+                insp: 6,
+                opcode: 2,
+                left: 1_000_000,
+                right: 1_000_000,
+            })
+        );
+
+        let mut t = with_words::<i128>(SQUARE_TWICE, &[1 << 20]);
+        assert_eq!(t.run_to_halt(), Ok(vec![1 << 80]));
+
+        let input: BigInt = "1000000000000".parse().unwrap();
+        let mut t = with_words(SQUARE_TWICE, &[input]);
+        let output = t.run_to_halt().unwrap();
+        assert_eq!(output[0].to_string(), format!("1{}", "0".repeat(48)));
+    }
+
+    #[test]
+    fn test_bigint_same_as_i64() {
+        let programs = [
+            (
+                "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+                0,
+            ),
+            ("3,9,8,9,10,9,4,9,99,-1,8", 8),
+            ("3,3,1107,-1,8,3,4,3,99", 3),
+        ];
+        for &(program, input) in programs.iter() {
+            let expected = Interpreter::new(program, &[input]).run_to_halt();
+            let output = with_words(program, &[BigInt::from(input)])
+                .run_to_halt()
+                .map(|output| output.iter().map(|v| v.to_i64().unwrap()).collect());
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn test_bigint_instrumented() {
+        // Moves the relative base past i64, then squares cell 1 into cell 2
+        // and outputs it, traced and with an undo log.
+        let program = "109,9223372036854775807,109,1,\
+                       22202,-9223372036854775807,-9223372036854775807,-9223372036854775806,\
+                       204,-9223372036854775806,99";
+        let buffer = SharedBuffer(Rc::new(RefCell::new(vec![])));
+        let mut t = with_words::<BigInt>(program, &[]);
+        t.start_trace(buffer.clone());
+        t.set_undo_log(Some(10));
+        let square = "85070591730234615847396907784232501249";
+        assert_eq!(t.run(), Ok(RunOutput::Output(square.parse().unwrap())));
+        assert_eq!(t.step_back(2), 2);
+        assert_eq!(t.read_memory(2), BigInt::from(109));
+        assert!(t.finish_trace().is_ok());
+
+        let trace = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        assert!(trace
+            .lines()
+            .nth(2)
+            .unwrap()
+            .contains(&format!("\"write\":{{\"address\":2,\"value\":{}}}", square)));
+    }
+
+    #[test]
+    fn test_parse_words() {
+        assert_eq!(parse_words::<i32>("1, 2,-3"), Ok(vec![1, 2, -3]));
+        assert_eq!(
+            parse_words::<i32>("104,1125899906842624,99"),
+            Err(IntcodeError::InvalidProgram { index: 1 })
+        );
+        assert_eq!(
+            parse_words::<i64>("104,1125899906842624,99"),
+            Ok(vec![104, 1125899906842624, 99])
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let buffer = SharedBuffer(Rc::new(RefCell::new(vec![])));
+        let mut t = Interpreter::new("109,5,21201,-1,7,0,4,5,99", &[]);
+        t.start_trace(buffer.clone());
+        assert_eq!(t.run_to_halt(), Ok(vec![14]));
+        assert!(t.finish_trace().is_ok());
+
+        let trace = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        assert_eq!(
+            trace.lines().collect::<Vec<_>>(),
+            vec![
+                "{\"insp\":0,\"opcode\":109,\"modes\":[1],\"operands\":[5],\"write\":null,\"relative_base\":0}",
+                "{\"insp\":2,\"opcode\":21201,\"modes\":[2,1,2],\"operands\":[7,7],\"write\":{\"address\":5,\"value\":14},\"relative_base\":5}",
+                "{\"insp\":6,\"opcode\":4,\"modes\":[0],\"operands\":[14],\"write\":null,\"relative_base\":5}",
+                "{\"insp\":8,\"opcode\":99,\"modes\":[],\"operands\":[],\"write\":null,\"relative_base\":5}",
+            ]
+        );
+    }
+}
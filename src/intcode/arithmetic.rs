@@ -0,0 +1,188 @@
+use super::{IntcodeError, Interpreter, Word};
+use std::fmt;
+use std::str::FromStr;
+
+// How add and multiply treat results that don't fit in a word. The policy
+// applies the same way in debug and release builds.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Arithmetic {
+    // Fail with IntcodeError::Overflow.
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+impl fmt::Display for Arithmetic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Arithmetic::Checked => "checked",
+            Arithmetic::Wrapping => "wrapping",
+            Arithmetic::Saturating => "saturating",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Arithmetic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "checked" => Ok(Arithmetic::Checked),
+            "wrapping" => Ok(Arithmetic::Wrapping),
+            "saturating" => Ok(Arithmetic::Saturating),
+            _ => Err(format!("unknown arithmetic '{}'", s)),
+        }
+    }
+}
+
+impl Arithmetic {
+    // Sum under this policy, None if it overflowed with Checked.
+    pub fn add<W: Word>(self, left: &W, right: &W) -> Option<W> {
+        match self {
+            Arithmetic::Checked => left.checked_add(right),
+            Arithmetic::Wrapping => Some(left.wrapping_add(right)),
+            Arithmetic::Saturating => Some(left.saturating_add(right)),
+        }
+    }
+
+    // Product under this policy, None if it overflowed with Checked.
+    pub fn mul<W: Word>(self, left: &W, right: &W) -> Option<W> {
+        match self {
+            Arithmetic::Checked => left.checked_mul(right),
+            Arithmetic::Wrapping => Some(left.wrapping_mul(right)),
+            Arithmetic::Saturating => Some(left.saturating_mul(right)),
+        }
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    fn overflow(&self, left: &W, right: &W) -> IntcodeError {
+        IntcodeError::Overflow {
+            insp: self.insp,
+            opcode: self.opcode(),
+            left: left.saturate(),
+            right: right.saturate(),
+        }
+    }
+
+    pub(super) fn add(&self, left: &W, right: &W) -> Result<W, IntcodeError> {
+        self.arithmetic
+            .add(left, right)
+            .ok_or_else(|| self.overflow(left, right))
+    }
+
+    pub(super) fn mul(&self, left: &W, right: &W) -> Result<W, IntcodeError> {
+        self.arithmetic
+            .mul(left, right)
+            .ok_or_else(|| self.overflow(left, right))
+    }
+
+    // The relative base is always checked, a wrapped or saturated base would
+    // point somewhere unrelated.
+    pub(super) fn adjust_relative_base(&mut self, offset: &W) -> Result<(), IntcodeError> {
+        self.relative_base = self
+            .relative_base
+            .checked_add(offset)
+            .ok_or_else(|| self.overflow(&self.relative_base, offset))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::RunOutput;
+
+    fn run(program: &str, arithmetic: Arithmetic) -> Result<RunOutput, IntcodeError> {
+        let mut t = Interpreter::new(program, &[]);
+        t.set_arithmetic(arithmetic);
+        t.run()
+    }
+
+    #[test]
+    fn test_policies() {
+        // Squares 2^32 and outputs the result.
+        let mul = "1102,4294967296,4294967296,7,4,7,99,0";
+        assert_eq!(
+            run(mul, Arithmetic::Checked),
+            Err(IntcodeError::Overflow {
+                insp: 0,
+                opcode: 1102,
+                left: 1 << 32,
+                right: 1 << 32,
+            })
+        );
+        assert_eq!(run(mul, Arithmetic::Wrapping), Ok(RunOutput::Output(0)));
+        assert_eq!(
+            run(mul, Arithmetic::Saturating),
+            Ok(RunOutput::Output(i64::MAX))
+        );
+
+        let add = "1101,-9223372036854775807,-2,7,4,7,99,0";
+        assert_eq!(
+            run(add, Arithmetic::Wrapping),
+            Ok(RunOutput::Output(i64::MAX))
+        );
+        assert_eq!(
+            run(add, Arithmetic::Saturating),
+            Ok(RunOutput::Output(i64::MIN))
+        );
+
+        // Close to the limit, but fits.
+        let fits = "1102,34915192,34915192,7,4,7,99,0";
+        assert_eq!(
+            run(fits, Arithmetic::Checked),
+            Ok(RunOutput::Output(1219070632396864))
+        );
+    }
+
+    #[test]
+    fn test_relative_base() {
+        let max = "109,9223372036854775807,109,1,99";
+        for &arithmetic in [Arithmetic::Checked, Arithmetic::Wrapping].iter() {
+            assert_eq!(
+                run(max, arithmetic),
+                Err(IntcodeError::Overflow {
+                    insp: 2,
+                    opcode: 109,
+                    left: i64::MAX,
+                    right: 1,
+                })
+            );
+        }
+
+        let address = "109,9223372036854775807,204,1,99";
+        assert_eq!(
+            run(address, Arithmetic::Wrapping),
+            Err(IntcodeError::InvalidAddress {
+                insp: 2,
+                opcode: 204,
+                address: i64::MAX,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for arithmetic in [
+            Arithmetic::Checked,
+            Arithmetic::Wrapping,
+            Arithmetic::Saturating,
+        ]
+        .iter()
+        {
+            assert_eq!(arithmetic.to_string().parse(), Ok(*arithmetic));
+        }
+        assert!("unchecked".parse::<Arithmetic>().is_err());
+    }
+}
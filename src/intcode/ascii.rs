@@ -0,0 +1,98 @@
+use super::{IntcodeError, Interpreter, RunOutput};
+
+// Output of a text program up to the next input request or halt.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AsciiOutput {
+    // Lines without the newline. Text after the last newline, like a prompt,
+    // is the last line.
+    pub lines: Vec<String>,
+    // Values outside of 0 to 127, which programs use for numeric answers.
+    pub values: Vec<i64>,
+    pub halted: bool,
+}
+
+impl Interpreter {
+    // Queue the character codes of the line followed by a newline. Lines
+    // with non-ASCII characters are rejected without queueing anything.
+    pub fn send_line(&mut self, line: &str) -> Result<(), String> {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii()) {
+            return Err(format!("non-ASCII character '{}' in input", c));
+        }
+        for b in line.bytes() {
+            self.put_input(i64::from(b));
+        }
+        self.put_input(10);
+        Ok(())
+    }
+
+    // Run until the program needs input or halts, collecting the output as
+    // text.
+    pub fn run_ascii(&mut self) -> Result<AsciiOutput, IntcodeError> {
+        let mut output = AsciiOutput::default();
+        let mut line = String::new();
+        loop {
+            let value = match self.run()? {
+                RunOutput::Output(value) => value,
+                RunOutput::Watch(hit) => match hit.output {
+                    Some(value) => value,
+                    None => continue,
+                },
+                RunOutput::NeedsInput => break,
+                RunOutput::Halt => {
+                    output.halted = true;
+                    break;
+                }
+            };
+            match value {
+                10 => output.lines.push(std::mem::take(&mut line)),
+                0..=127 => line.push(value as u8 as char),
+                _ => output.values.push(value),
+            }
+        }
+        if !line.is_empty() {
+            output.lines.push(line);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_echo() {
+        // Echoes input up to a newline, then outputs 1000 and halts.
+        let program = "3,100,4,100,1008,100,10,101,1006,101,0,104,1000,99";
+        let mut t = Interpreter::new(program, &[]);
+        assert_eq!(t.run_ascii(), Ok(AsciiOutput::default()));
+        assert_eq!(t.send_line("hi there"), Ok(()));
+        assert_eq!(
+            t.run_ascii(),
+            Ok(AsciiOutput {
+                lines: vec!["hi there".to_string()],
+                values: vec![1000],
+                halted: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let mut t = Interpreter::new("3,0,99", &[]);
+        assert_eq!(
+            t.send_line("café"),
+            Err("non-ASCII character 'é' in input".to_string())
+        );
+        assert_eq!(t.pending_input(), &[]);
+    }
+
+    #[test]
+    fn test_prompt() {
+        // Prints "ok", then the prompt "> " and waits for input.
+        let mut t = Interpreter::new("104,111,104,107,104,10,104,62,104,32,3,0,99", &[]);
+        let output = t.run_ascii().unwrap();
+        assert_eq!(output.lines, vec!["ok", "> "]);
+        assert!(!output.halted);
+    }
+}
@@ -0,0 +1,306 @@
+use super::{Opcode, OpcodeMode};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// Opcodes with a mnemonic, see Opcode::mnemonic.
+const OPCODES: [i64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssembleError {
+    // Line number starting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AssembleError {}
+
+// Number or label reference with an optional offset, e.g. `12`, `loop` or `buffer+2`.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(i64),
+    Label(String, i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Operand {
+    mode: OpcodeMode,
+    value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Statement {
+    Instruction(i64, Vec<Operand>),
+    Data(Vec<Value>),
+}
+
+impl Statement {
+    fn len(&self) -> usize {
+        match self {
+            Statement::Instruction(_, operands) => operands.len() + 1,
+            Statement::Data(values) => values.len(),
+        }
+    }
+}
+
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    let s = s.trim();
+    if let Ok(number) = s.parse() {
+        return Ok(Value::Number(number));
+    }
+
+    // Split label from its offset.
+    let (label, offset) = match s.find(['+', '-']) {
+        Some(i) => (&s[..i], &s[i..]),
+        None => (s, ""),
+    };
+    let label = label.trim();
+    if !is_label(label) {
+        return Err(format!("invalid value '{}'", s));
+    }
+    let offset = match offset.replace(' ', "").trim_start_matches('+') {
+        "" => 0,
+        offset => offset
+            .parse()
+            .map_err(|_| format!("invalid offset in '{}'", s))?,
+    };
+    Ok(Value::Label(label.to_string(), offset))
+}
+
+fn parse_operand(s: &str) -> Result<Operand, String> {
+    let s = s.trim();
+    let (mode, value) = if let Some(value) = s.strip_prefix('#') {
+        (OpcodeMode::Immediate, value)
+    } else if s.starts_with('[') && s.ends_with(']') {
+        (OpcodeMode::Positional, &s[1..s.len() - 1])
+    } else if let Some(value) = s.strip_prefix("rb") {
+        (OpcodeMode::Relative, value)
+    } else {
+        return Err(format!(
+            "operand '{}' must be #value, [address] or rb+offset",
+            s
+        ));
+    };
+
+    let value = parse_value(value)?;
+    Ok(Operand { mode, value })
+}
+
+fn parse_statement(s: &str) -> Result<Statement, String> {
+    let (name, rest) = match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim()),
+        None => (s, ""),
+    };
+    let args: Vec<&str> = if rest.is_empty() {
+        vec![]
+    } else {
+        rest.split(',').collect()
+    };
+
+    if name == ".data" {
+        if args.is_empty() {
+            return Err(".data needs at least one value".to_string());
+        }
+        let values = args
+            .into_iter()
+            .map(parse_value)
+            .collect::<Result<_, _>>()?;
+        return Ok(Statement::Data(values));
+    }
+
+    let opcode = OPCODES
+        .iter()
+        .map(|&number| Opcode::new(number).unwrap())
+        .find(|opcode| opcode.mnemonic() == Some(name))
+        .ok_or_else(|| format!("unknown mnemonic '{}'", name))?;
+    let param_count = opcode.param_count().unwrap();
+    if args.len() != param_count {
+        return Err(format!(
+            "{} takes {} operands, got {}",
+            name,
+            param_count,
+            args.len()
+        ));
+    }
+    let operands: Vec<Operand> = args
+        .into_iter()
+        .map(parse_operand)
+        .collect::<Result<_, _>>()?;
+    if let Some(i) = opcode.write_param() {
+        if operands[i].mode == OpcodeMode::Immediate {
+            return Err(format!("{} can't write to an immediate operand", name));
+        }
+    }
+    Ok(Statement::Instruction(opcode.number, operands))
+}
+
+// Assemble source into memory cells.
+//
+// Each line holds an optional `label:` followed by an instruction or a
+// `.data` directive, `;` starts a comment. Operands are written as in the
+// disassembler listing: `#5` immediate, `[12]` positional and `rb+3` relative.
+// Labels can be used in place of numbers, optionally with an offset.
+pub fn assemble_cells(source: &str) -> Result<Vec<i64>, AssembleError> {
+    let mut labels: HashMap<String, i64> = HashMap::new();
+    let mut statements = vec![];
+    let mut address = 0;
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let error = |message| AssembleError {
+            line: line_number,
+            message,
+        };
+
+        let mut line = line.split(';').next().unwrap().trim();
+        if let Some(colon) = line.find(':') {
+            let label = line[..colon].trim();
+            if !is_label(label) {
+                return Err(error(format!("invalid label '{}'", label)));
+            }
+            if labels.insert(label.to_string(), address).is_some() {
+                return Err(error(format!("duplicate label '{}'", label)));
+            }
+            line = line[colon + 1..].trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let statement = parse_statement(line).map_err(error)?;
+        address += statement.len() as i64;
+        statements.push((line_number, statement));
+    }
+
+    let mut cells = vec![];
+    for (line_number, statement) in statements {
+        let resolve = |value: &Value| match value {
+            Value::Number(number) => Ok(*number),
+            Value::Label(label, offset) => match labels.get(label) {
+                Some(address) => Ok(address + offset),
+                None => Err(AssembleError {
+                    line: line_number,
+                    message: format!("undefined label '{}'", label),
+                }),
+            },
+        };
+
+        match statement {
+            Statement::Instruction(number, operands) => {
+                let modes: i64 = operands
+                    .iter()
+                    .enumerate()
+                    .map(|(i, operand)| operand.mode.digit() * 10_i64.pow(i as u32 + 2))
+                    .sum();
+                cells.push(number + modes);
+                for operand in operands.iter() {
+                    cells.push(resolve(&operand.value)?);
+                }
+            }
+            Statement::Data(values) => {
+                for value in values.iter() {
+                    cells.push(resolve(value)?);
+                }
+            }
+        }
+    }
+
+    Ok(cells)
+}
+
+// Assemble source into a comma separated program for Interpreter::new.
+pub fn assemble(source: &str) -> Result<String, AssembleError> {
+    let cells = assemble_cells(source)?;
+    Ok(cells
+        .iter()
+        .map(|cell| cell.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{disassemble, Interpreter};
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            ; Output the sum of two inputs.
+                    in [a]
+                    in [b]
+                    add [a], [b], [sum]
+                    out [sum]
+                    hlt
+            a:      .data 0
+            b:      .data 0
+            sum:    .data 0
+        ";
+        let program = assemble(source).unwrap();
+        assert_eq!(program, "3,11,3,12,1,11,12,13,4,13,99,0,0,0");
+        let mut t = Interpreter::new(&program, &[3, 4]);
+        assert_eq!(t.run_to_halt(), Ok(vec![7]));
+    }
+
+    #[test]
+    fn test_labels_and_modes() {
+        let source = "
+                    arb #stack
+            loop:   jz [counter], #done
+                    out rb-1
+                    add [counter], #-1, [counter]
+                    jnz #1, #loop
+            done:   hlt
+            counter: .data 3
+                    .data 42, done, counter+1
+            stack:
+        ";
+        let program = assemble(source).unwrap();
+        let mut t = Interpreter::new(&program, &[]);
+        assert_eq!(t.run_to_halt(), Ok(vec![16, 16, 16]));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let program = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let listing: String = disassemble(program)
+            .unwrap()
+            .lines()
+            .map(|line| format!("{}\n", line.trim_start().split_once("  ").unwrap().1))
+            .collect();
+        assert_eq!(assemble(&listing), Ok(program.to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source| assemble(source).unwrap_err();
+        assert_eq!(error("add #1, #2").line, 1);
+        assert_eq!(error("hlt\nfoo [1]").message, "unknown mnemonic 'foo'");
+        assert_eq!(
+            error("in #1").message,
+            "in can't write to an immediate operand"
+        );
+        assert_eq!(error("out [nowhere]").message, "undefined label 'nowhere'");
+        assert_eq!(error("a: hlt\na: hlt").message, "duplicate label 'a'");
+        assert_eq!(
+            error("out 5").message,
+            "operand '5' must be #value, [address] or rb+offset"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use super::{IntcodeError, Interpreter, RunOutput, Word};
+use std::time::Instant;
+
+// Instructions executed between reads of the clock in run_until.
+const CLOCK_INTERVAL: u64 = 1024;
+
+// Result of run_with_budget and run_until.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Budgeted<W = i64> {
+    // The run ended before the budget was used up.
+    Done(RunOutput<W>),
+    // Step or time budget used up. Running again resumes from the next
+    // instruction.
+    Exhausted,
+}
+
+impl<W: Word> Interpreter<W> {
+    // Like run, but pauses with Budgeted::Exhausted once max_steps
+    // instructions have been executed. Running again resumes from there.
+    pub fn run_with_budget(&mut self, max_steps: u64) -> Result<Budgeted<W>, IntcodeError> {
+        for _ in 0..max_steps {
+            if let Some(output) = self.step()? {
+                return Ok(Budgeted::Done(output));
+            }
+        }
+        Ok(Budgeted::Exhausted)
+    }
+
+    // Like run, but pauses with Budgeted::Exhausted after the deadline. The
+    // clock is checked every CLOCK_INTERVAL instructions.
+    pub fn run_until(&mut self, deadline: Instant) -> Result<Budgeted<W>, IntcodeError> {
+        while Instant::now() < deadline {
+            match self.run_with_budget(CLOCK_INTERVAL)? {
+                Budgeted::Exhausted => {}
+                done => return Ok(done),
+            }
+        }
+        Ok(Budgeted::Exhausted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // Outputs 1, 2, 3, ... forever.
+    const COUNTER: &str = "1001,9,1,9,4,9,1105,1,0,0";
+
+    #[test]
+    fn test_budget() {
+        let mut t = Interpreter::new(COUNTER, &[]);
+        assert_eq!(t.run_with_budget(1), Ok(Budgeted::Exhausted));
+        assert_eq!(t.insp(), 4);
+        assert_eq!(
+            t.run_with_budget(1),
+            Ok(Budgeted::Done(RunOutput::Output(1)))
+        );
+        assert_eq!(t.run_with_budget(2), Ok(Budgeted::Exhausted));
+        assert_eq!(
+            t.run_with_budget(2),
+            Ok(Budgeted::Done(RunOutput::Output(2)))
+        );
+        assert_eq!(t.run_with_budget(0), Ok(Budgeted::Exhausted));
+
+        let mut t = Interpreter::new("104,5,99", &[]);
+        assert_eq!(
+            t.run_with_budget(10),
+            Ok(Budgeted::Done(RunOutput::Output(5)))
+        );
+        assert_eq!(t.run_with_budget(10), Ok(Budgeted::Done(RunOutput::Halt)));
+    }
+
+    #[test]
+    fn test_deadline() {
+        // Jumps to itself forever.
+        let mut t = Interpreter::new("1105,1,0", &[]);
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert_eq!(t.run_until(deadline), Ok(Budgeted::Exhausted));
+        assert!(Instant::now() >= deadline);
+
+        let mut t = Interpreter::new(COUNTER, &[]);
+        let deadline = Instant::now() + Duration::from_secs(60);
+        assert_eq!(
+            t.run_until(deadline),
+            Ok(Budgeted::Done(RunOutput::Output(1)))
+        );
+    }
+}
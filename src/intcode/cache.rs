@@ -0,0 +1,102 @@
+use super::{IntcodeError, Interpreter, Opcode, Word};
+
+// Decoded opcodes of the program by address. Parameters are read from memory
+// on every execution, so only writes to the opcode itself drop a slot.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InstructionCache {
+    opcodes: Vec<Option<Opcode>>,
+}
+
+impl<W: Word> Interpreter<W> {
+    // Decode each opcode of the program only once, up front. Clones keep
+    // the decoded opcodes, so a template cloned for every run only decodes
+    // once in total.
+    pub fn set_instruction_cache(&mut self, enabled: bool) {
+        self.cache = if enabled {
+            let opcodes = self
+                .memory
+                .dense_cells()
+                .iter()
+                .map(|cell| cell.to_i64().and_then(Opcode::new))
+                .collect();
+            Some(InstructionCache { opcodes })
+        } else {
+            None
+        };
+    }
+
+    // Opcode of the instruction at insp.
+    #[inline(always)]
+    pub(super) fn fetch(&mut self) -> Result<Opcode, IntcodeError> {
+        let insp = self.insp;
+        if let Some(Some(opcode)) = self.cache.as_ref().and_then(|c| c.opcodes.get(insp)) {
+            return Ok(*opcode);
+        }
+
+        let opcode = self
+            .read_memory(insp)
+            .to_i64()
+            .and_then(Opcode::new)
+            .ok_or_else(|| IntcodeError::InvalidMode {
+                insp,
+                opcode: self.opcode(),
+            })?;
+        // Only the program region is cached, jumps far out of it are rare.
+        if let Some(slot) = self.cache.as_mut().and_then(|c| c.opcodes.get_mut(insp)) {
+            *slot = Some(opcode);
+        }
+        Ok(opcode)
+    }
+
+    // Write memory, dropping the cached opcode at the address.
+    pub(super) fn store(&mut self, address: usize, value: W) {
+        self.memory.write(address, value);
+        if let Some(slot) = self.cache.as_mut().and_then(|c| c.opcodes.get_mut(address)) {
+            *slot = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::RunOutput;
+
+    #[test]
+    fn test_same_results() {
+        let programs = [
+            (
+                "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+                vec![],
+            ),
+            // Writes the input into its own opcode at 6 before running it.
+            ("3,225,1,225,6,6,1100,1,238,225,104,0,99", vec![1]),
+            ("3,9,8,9,10,9,4,9,99,-1,8", vec![8]),
+        ];
+        for (program, input) in programs.iter() {
+            let mut plain = Interpreter::new(program, input);
+            let mut cached = Interpreter::new(program, input);
+            cached.set_instruction_cache(true);
+            assert_eq!(cached.run_to_halt(), plain.run_to_halt());
+            assert_eq!(cached.snapshot(), plain.snapshot());
+        }
+    }
+
+    #[test]
+    fn test_invalidate() {
+        // Loop that outputs 7, then patches the output into an output of 8.
+        let program = "104,7,1101,0,8,1,1105,1,0";
+        let mut t = Interpreter::new(program, &[]);
+        t.set_instruction_cache(true);
+        assert_eq!(t.run(), Ok(RunOutput::Output(7)));
+        assert_eq!(t.run(), Ok(RunOutput::Output(8)));
+        assert_eq!(t.run(), Ok(RunOutput::Output(8)));
+
+        // Outputs 7 forever, until patched from outside.
+        let mut t = Interpreter::new("104,7,1105,1,0", &[]);
+        t.set_instruction_cache(true);
+        assert_eq!(t.run(), Ok(RunOutput::Output(7)));
+        t.write_memory(1, 5);
+        assert_eq!(t.run(), Ok(RunOutput::Output(5)));
+    }
+}
@@ -0,0 +1,336 @@
+use super::{decode, parse_program, Instruction, IntcodeError, OpcodeMode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryInto;
+use std::fmt::Write as _;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Edge {
+    FallThrough(usize),
+    Jump(usize),
+    // Jump to a function that returns to `ret`, see call_return.
+    Call { target: usize, ret: usize },
+    // Jump to a target computed at run time.
+    Unresolved,
+    // Jump to a constant that is not an address, which fails at run time.
+    InvalidTarget(i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub instructions: Vec<Instruction>,
+    pub edges: Vec<Edge>,
+}
+
+impl BasicBlock {
+    // Address after the last instruction of the block.
+    pub fn end(&self) -> usize {
+        self.instructions
+            .last()
+            .map_or(self.start, |instruction| instruction.next_address())
+    }
+}
+
+// Edges leaving a jump instruction. Jumps with an immediate condition only
+// get the edge that is actually taken.
+fn jump_edges(instruction: &Instruction) -> Vec<Edge> {
+    let modes = instruction.modes();
+    let (condition, target) = (instruction.params[0], instruction.params[1]);
+    let jumps = match (modes[0], instruction.opcode.number) {
+        (OpcodeMode::Immediate, 5) => Some(condition != 0),
+        (OpcodeMode::Immediate, _) => Some(condition == 0),
+        _ => None,
+    };
+
+    let mut edges = vec![];
+    if jumps != Some(false) {
+        if modes[1] != OpcodeMode::Immediate {
+            edges.push(Edge::Unresolved);
+        } else if let Ok(target) = target.try_into() {
+            edges.push(Edge::Jump(target));
+        } else {
+            edges.push(Edge::InvalidTarget(target));
+        }
+    }
+    if jumps != Some(true) {
+        edges.push(Edge::FallThrough(instruction.next_address()));
+    }
+    edges
+}
+
+// Constant written to rb+0 by the instruction, which is how callers pass the
+// return address.
+fn return_address_write(instruction: &Instruction) -> Option<i64> {
+    let modes = instruction.modes();
+    if instruction.params.len() != 3
+        || modes[0] != OpcodeMode::Immediate
+        || modes[1] != OpcodeMode::Immediate
+        || modes[2] != OpcodeMode::Relative
+        || instruction.params[2] != 0
+    {
+        return None;
+    }
+    let (a, b) = (instruction.params[0], instruction.params[1]);
+    match instruction.opcode.number {
+        1 => a.checked_add(b),
+        2 => a.checked_mul(b),
+        _ => None,
+    }
+}
+
+// Return address of a block that calls a function: the block ends with an
+// unconditional immediate jump and stores the address after the jump to
+// rb+0 beforehand.
+pub fn call_return(instructions: &[Instruction]) -> Option<(usize, usize)> {
+    let (jump, rest) = instructions.split_last()?;
+    if !matches!(jump.opcode.number, 5 | 6) {
+        return None;
+    }
+    let target = match jump_edges(jump).as_slice() {
+        [Edge::Jump(target)] => *target,
+        _ => return None,
+    };
+    let ret = jump.next_address();
+    rest.iter()
+        .any(|instruction| return_address_write(instruction) == Some(ret as i64))
+        .then_some((target, ret))
+}
+
+// Control-flow graph of the code reachable from address 0 through fall-through,
+// immediate jump and call return edges. The program is analyzed as loaded,
+// code written at run time is not seen.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cfg {
+    pub blocks: BTreeMap<usize, BasicBlock>,
+}
+
+impl Cfg {
+    pub fn build(cells: &[i64]) -> Cfg {
+        Cfg::build_with_roots(cells, &[0])
+    }
+
+    // Control-flow graph of the code reachable from any of the roots.
+    pub fn build_with_roots(cells: &[i64], roots: &[usize]) -> Cfg {
+        // Return addresses are only reachable through computed jumps, so they
+        // are added as roots once their calls are found.
+        let mut roots = roots.to_vec();
+        loop {
+            let cfg = Cfg::build_from(cells, &roots);
+            let returns: Vec<usize> = cfg
+                .blocks
+                .values()
+                .flat_map(|block| block.edges.iter())
+                .filter_map(|edge| match edge {
+                    Edge::Call { ret, .. } if !roots.contains(ret) => Some(*ret),
+                    _ => None,
+                })
+                .collect();
+            if returns.is_empty() {
+                return cfg;
+            }
+            roots.extend(returns);
+        }
+    }
+
+    fn build_from(cells: &[i64], roots: &[usize]) -> Cfg {
+        // Decode reachable instructions, starting blocks at jump targets and
+        // after jumps.
+        let mut instructions = BTreeMap::new();
+        let mut leaders: BTreeSet<usize> = roots.iter().cloned().collect();
+        let mut pending = roots.to_vec();
+        while let Some(address) = pending.pop() {
+            if instructions.contains_key(&address) {
+                continue;
+            }
+            let instruction = match decode(cells, address) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+            match instruction.opcode.number {
+                5 | 6 => {
+                    for edge in jump_edges(&instruction) {
+                        if let Edge::Jump(target) | Edge::FallThrough(target) = edge {
+                            leaders.insert(target);
+                            pending.push(target);
+                        }
+                    }
+                }
+                99 => {}
+                _ => pending.push(instruction.next_address()),
+            }
+            instructions.insert(address, instruction);
+        }
+
+        let mut cfg = Cfg::default();
+        for &start in leaders.iter().filter(|a| instructions.contains_key(a)) {
+            let mut block = BasicBlock {
+                start,
+                instructions: vec![],
+                edges: vec![],
+            };
+            let mut address = start;
+            while let Some(instruction) = instructions.get(&address) {
+                block.instructions.push(instruction.clone());
+                match instruction.opcode.number {
+                    5 | 6 => {
+                        block.edges = match call_return(&block.instructions) {
+                            Some((target, ret)) => vec![Edge::Call { target, ret }],
+                            None => jump_edges(instruction),
+                        };
+                        break;
+                    }
+                    99 => break,
+                    _ => {}
+                }
+                address = instruction.next_address();
+                if leaders.contains(&address) {
+                    block.edges.push(Edge::FallThrough(address));
+                    break;
+                }
+            }
+            cfg.blocks.insert(start, block);
+        }
+        cfg
+    }
+
+    pub fn from_program(program: &str) -> Result<Cfg, IntcodeError> {
+        Ok(Cfg::build(&parse_program(program)?))
+    }
+
+    // Graphviz graph with one node per block. Unresolved jumps point to a
+    // `?` node of their own, invalid targets to a red node with the target.
+    pub fn to_dot(&self) -> String {
+        let mut s = String::new();
+        writeln!(s, "digraph intcode {{").unwrap();
+        writeln!(s, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+        for block in self.blocks.values() {
+            let label: String = block
+                .instructions
+                .iter()
+                .map(|instruction| format!("{}: {}\\l", instruction.address, instruction))
+                .collect();
+            writeln!(s, "  b{} [label=\"{}\"];", block.start, label).unwrap();
+            for edge in block.edges.iter() {
+                match edge {
+                    Edge::FallThrough(target) => {
+                        writeln!(s, "  b{} -> b{} [style=dotted];", block.start, target).unwrap()
+                    }
+                    Edge::Jump(target) => {
+                        writeln!(s, "  b{} -> b{};", block.start, target).unwrap()
+                    }
+                    Edge::Call { target, ret } => {
+                        writeln!(s, "  b{} -> b{} [color=blue];", block.start, target).unwrap();
+                        writeln!(s, "  b{} -> b{} [style=dotted];", block.start, ret).unwrap();
+                    }
+                    Edge::Unresolved => {
+                        writeln!(s, "  u{} [label=\"?\", shape=circle];", block.start).unwrap();
+                        writeln!(s, "  b{} -> u{} [style=dashed];", block.start, block.start)
+                            .unwrap();
+                    }
+                    Edge::InvalidTarget(target) => {
+                        writeln!(
+                            s,
+                            "  x{} [label=\"{}\", shape=octagon, color=red];",
+                            block.start, target
+                        )
+                        .unwrap();
+                        writeln!(s, "  b{} -> x{} [color=red];", block.start, block.start).unwrap();
+                    }
+                }
+            }
+        }
+        writeln!(s, "}}").unwrap();
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::fixtures::IS_FIVE;
+
+    #[test]
+    fn test_blocks() {
+        let cfg = Cfg::from_program(IS_FIVE).unwrap();
+        let blocks: Vec<(usize, usize, &[Edge])> = cfg
+            .blocks
+            .values()
+            .map(|b| (b.start, b.end(), b.edges.as_slice()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                (0, 9, &[Edge::Jump(12), Edge::FallThrough(9)][..]),
+                (9, 12, &[][..]),
+                (12, 15, &[][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_loop_and_unresolved() {
+        // Counts down cell 12, then jumps to the address in relative base 0.
+        let cfg = Cfg::from_program("4,12,1001,12,-1,12,1005,12,0,2105,1,0,3").unwrap();
+        let edges: Vec<&[Edge]> = cfg.blocks.values().map(|b| b.edges.as_slice()).collect();
+        assert_eq!(
+            edges,
+            vec![
+                &[Edge::Jump(0), Edge::FallThrough(9)][..],
+                &[Edge::Unresolved][..]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_target() {
+        let cfg = Cfg::from_program("3,9,1005,9,-4,99,1106,0,-1,0").unwrap();
+        let edges: Vec<&[Edge]> = cfg.blocks.values().map(|b| b.edges.as_slice()).collect();
+        assert_eq!(
+            edges,
+            vec![
+                &[Edge::InvalidTarget(-4), Edge::FallThrough(5)][..],
+                &[][..]
+            ]
+        );
+        assert!(cfg
+            .to_dot()
+            .contains("  x0 [label=\"-4\", shape=octagon, color=red];\n  b0 -> x0 [color=red];\n"));
+    }
+
+    #[test]
+    fn test_call() {
+        // Calls the function at 10, which returns through rb+0.
+        let cfg = Cfg::from_program("109,100,21101,0,9,0,1105,1,10,99,2105,1,0").unwrap();
+        let edges: Vec<(usize, &[Edge])> = cfg
+            .blocks
+            .values()
+            .map(|b| (b.start, b.edges.as_slice()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                (0, &[Edge::Call { target: 10, ret: 9 }][..]),
+                (9, &[][..]),
+                (10, &[Edge::Unresolved][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let cfg = Cfg::from_program("3,9,1005,9,6,99,2105,1,0,0").unwrap();
+        assert_eq!(
+            cfg.to_dot(),
+            "digraph intcode {\n\
+             \x20 node [shape=box, fontname=\"monospace\"];\n\
+             \x20 b0 [label=\"0: in [9]\\l2: jnz [9], #6\\l\"];\n\
+             \x20 b0 -> b6;\n\
+             \x20 b0 -> b5 [style=dotted];\n\
+             \x20 b5 [label=\"5: hlt\\l\"];\n\
+             \x20 b6 [label=\"6: jnz #1, rb+0\\l\"];\n\
+             \x20 u6 [label=\"?\", shape=circle];\n\
+             \x20 b6 -> u6 [style=dashed];\n\
+             }\n"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use super::{decode, Instruction, Interpreter, Line, Word};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+// Addresses touched by a program run. Executed addresses are the addresses
+// of instruction opcodes, reads and writes are parameter accesses.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Coverage {
+    // Instruction last executed at each address.
+    pub executed: BTreeMap<usize, Instruction>,
+    pub read: BTreeSet<usize>,
+    pub written: BTreeSet<usize>,
+}
+
+impl Coverage {
+    pub fn record(&mut self, instruction: Instruction, reads: &[usize], write: Option<usize>) {
+        self.executed.insert(instruction.address, instruction);
+        self.read.extend(reads);
+        self.written.extend(write);
+    }
+
+    // Combine with the coverage of another run of the same program.
+    pub fn merge(&mut self, other: &Coverage) {
+        self.executed.extend(other.executed.clone());
+        self.read.extend(&other.read);
+        self.written.extend(&other.written);
+    }
+
+    // `x`, `r` and `w` for executed, read and written, `-` if not.
+    pub fn flags(&self, address: usize) -> String {
+        let flag = |set: &BTreeSet<usize>, c| if set.contains(&address) { c } else { '-' };
+        [
+            if self.executed.contains_key(&address) {
+                'x'
+            } else {
+                '-'
+            },
+            flag(&self.read, 'r'),
+            flag(&self.written, 'w'),
+        ]
+        .iter()
+        .collect()
+    }
+
+    // One line with the flags of each touched address.
+    pub fn summary(&self) -> String {
+        let mut addresses: BTreeSet<usize> = self.executed.keys().cloned().collect();
+        addresses.extend(&self.read);
+        addresses.extend(&self.written);
+        addresses
+            .into_iter()
+            .map(|address| format!("{:>6}  {}\n", address, self.flags(address)))
+            .collect()
+    }
+
+    // Disassembly of the program with the flags of each line. Executed
+    // addresses show the instruction as it was executed, which may differ from
+    // the cells in self-modifying programs. Other cells that were accessed as
+    // data are listed as data.
+    pub fn listing(&self, cells: &[i64]) -> String {
+        let mut s = String::new();
+        let mut address = 0;
+        while address < cells.len() {
+            let data = self.read.contains(&address) || self.written.contains(&address);
+            let instruction = match self.executed.get(&address) {
+                Some(instruction) => Some(instruction.clone()),
+                None => decode(cells, address).filter(|_| !data),
+            };
+            let line = match instruction {
+                Some(instruction) => Line::Instruction(instruction),
+                None => Line::Data {
+                    address,
+                    value: cells[address],
+                },
+            };
+            writeln!(s, "{} {}", self.flags(address), line).unwrap();
+            address = match line {
+                Line::Instruction(instruction) => instruction.next_address(),
+                Line::Data { .. } => address + 1,
+            };
+        }
+        s
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    // Record executed, read and written addresses from now on.
+    pub fn start_coverage(&mut self) {
+        self.coverage = Some(Coverage::default());
+    }
+
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    // Stop recording coverage and return it.
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::fixtures::IS_FIVE;
+    use crate::intcode::parse_program;
+
+    fn coverage(input: i64) -> Coverage {
+        let mut t = Interpreter::new(IS_FIVE, &[input]);
+        t.start_coverage();
+        t.run_to_halt().unwrap();
+        t.take_coverage().unwrap()
+    }
+
+    #[test]
+    fn test_coverage() {
+        let coverage = coverage(5);
+        assert_eq!(
+            coverage.executed.keys().cloned().collect::<Vec<_>>(),
+            vec![0, 2, 6, 12, 14]
+        );
+        assert_eq!(
+            coverage.summary(),
+            "     0  x--\n     2  x--\n     6  x--\n    12  x--\n    14  x--\n    15  -rw\n    16  -rw\n"
+        );
+    }
+
+    #[test]
+    fn test_listing() {
+        let mut coverage = coverage(5);
+        let cells = parse_program(IS_FIVE).unwrap();
+        assert_eq!(
+            coverage.listing(&cells),
+            "x--      0  in [15]\n\
+             x--      2  eq [15], #5, [16]\n\
+             x--      6  jnz [16], #12\n\
+             ---      9  out #0\n\
+             ---     11  hlt\n\
+             x--     12  out #1\n\
+             x--     14  hlt\n\
+             -rw     15  .data 0\n\
+             -rw     16  .data 0\n"
+        );
+
+        coverage.merge(&self::coverage(1));
+        assert!(coverage
+            .listing(&cells)
+            .lines()
+            .all(|line| !line.starts_with("---")));
+    }
+}
@@ -0,0 +1,296 @@
+use super::{IntcodeError, Interpreter, Opcode, OpcodeMode, Word};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Param {
+    // Value resolved by the parameter mode, like the inputs of add.
+    Read,
+    // Address written after the handler ran, immediate mode is an error.
+    Write,
+}
+
+// Called with the read parameter values and the write parameter values.
+type Handler<W> = Rc<dyn Fn(&[W], &mut [W])>;
+
+// Instruction added with Interpreter::register_opcode. Clones of the
+// interpreter share the handler.
+pub struct CustomOpcode<W = i64> {
+    params: Vec<Param>,
+    handler: Handler<W>,
+}
+
+impl<W> Clone for CustomOpcode<W> {
+    fn clone(&self) -> Self {
+        CustomOpcode {
+            params: self.params.clone(),
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<W> fmt::Debug for CustomOpcode<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CustomOpcode")
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RegisterError {
+    // Opcode number is one of the built-in instructions.
+    Builtin(i64),
+    AlreadyRegistered(i64),
+    // Opcode numbers are the two lowest digits of the opcode.
+    InvalidNumber(i64),
+    // Only three parameters have mode digits.
+    TooManyParams(usize),
+    // Like built-in instructions, at most one parameter is written.
+    TooManyWrites(usize),
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegisterError::Builtin(number) => write!(f, "opcode {} is built in", number),
+            RegisterError::AlreadyRegistered(number) => {
+                write!(f, "opcode {} is already registered", number)
+            }
+            RegisterError::InvalidNumber(number) => write!(f, "invalid opcode number {}", number),
+            RegisterError::TooManyParams(count) => {
+                write!(f, "{} parameters, at most 3 are supported", count)
+            }
+            RegisterError::TooManyWrites(count) => {
+                write!(f, "{} write parameters, at most 1 is supported", count)
+            }
+        }
+    }
+}
+
+impl Error for RegisterError {}
+
+impl<W: Word> Interpreter<W> {
+    // Add an instruction with opcode number 1 to 98. Parameter modes come
+    // from the opcode like for built-in instructions. The handler gets the
+    // values of the read parameters and sets the value of the write
+    // parameter, which starts out as the current contents of its cell.
+    // Custom instructions are traced, undone, watched and profiled like
+    // built-in ones, but transpile doesn't compile them. Loop detection
+    // treats them like I/O, as handlers may talk to the host.
+    pub fn register_opcode<F>(
+        &mut self,
+        number: i64,
+        params: &[Param],
+        handler: F,
+    ) -> Result<(), RegisterError>
+    where
+        F: Fn(&[W], &mut [W]) + 'static,
+    {
+        if !(1..100).contains(&number) {
+            return Err(RegisterError::InvalidNumber(number));
+        }
+        if Opcode::new(number).and_then(|o| o.param_count()).is_some() {
+            return Err(RegisterError::Builtin(number));
+        }
+        if self.custom_opcodes.contains_key(&number) {
+            return Err(RegisterError::AlreadyRegistered(number));
+        }
+        if params.len() > 3 {
+            return Err(RegisterError::TooManyParams(params.len()));
+        }
+        let writes = params.iter().filter(|&&p| p == Param::Write).count();
+        if writes > 1 {
+            return Err(RegisterError::TooManyWrites(writes));
+        }
+        let custom = CustomOpcode {
+            params: params.to_vec(),
+            handler: Rc::new(handler),
+        };
+        self.custom_opcodes.insert(number, custom);
+        Ok(())
+    }
+
+    // Number of parameters and index of the written one, for built-in and
+    // registered opcodes. None for unknown opcodes.
+    pub(super) fn signature(&self, opcode: Opcode) -> Option<(usize, Option<usize>)> {
+        if let Some(count) = opcode.param_count() {
+            return Some((count, opcode.write_param()));
+        }
+        let custom = self.custom_opcodes.get(&opcode.number)?;
+        let write = custom.params.iter().position(|&p| p == Param::Write);
+        Some((custom.params.len(), write))
+    }
+
+    // Execute a custom instruction at insp. Returns false if the opcode is
+    // not registered.
+    pub(super) fn execute_custom(&mut self, opcode: Opcode) -> Result<bool, IntcodeError> {
+        let custom = match self.custom_opcodes.get(&opcode.number) {
+            Some(custom) => custom.clone(),
+            None => return Ok(false),
+        };
+        let modes = [opcode.mode1, opcode.mode2, opcode.mode3];
+        let mut reads = vec![];
+        let mut addresses = vec![];
+        for (i, param) in custom.params.iter().enumerate() {
+            let operand = self.read_memory(self.insp + i + 1);
+            match param {
+                Param::Read => reads.push(self.read_param(&operand, modes[i])?),
+                Param::Write if modes[i] == OpcodeMode::Immediate => {
+                    return Err(IntcodeError::ImmediateWrite {
+                        insp: self.insp,
+                        opcode: self.opcode(),
+                        operand: operand.saturate(),
+                    })
+                }
+                Param::Write => addresses.push(self.param_address(&operand, modes[i])?),
+            }
+        }
+
+        let mut values: Vec<W> = addresses.iter().map(|&a| self.read_memory(a)).collect();
+        (custom.handler)(&reads, &mut values);
+        for (address, value) in addresses.into_iter().zip(values) {
+            self.store(address, value);
+        }
+        self.insp += custom.params.len() + 1;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{Access, Budgeted, RunOutput, WatchHit, WatchKind};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_debug_print() {
+        // Prints cell 7 and the constant 5 with opcode 42, then halts.
+        let printed = Rc::new(RefCell::new(vec![]));
+        let log = printed.clone();
+        let mut t = Interpreter::new("42,7,142,5,99,0,0,3", &[]);
+        t.register_opcode(42, &[Param::Read], move |reads, _| {
+            log.borrow_mut().push(reads[0])
+        })
+        .unwrap();
+        assert_eq!(t.run_to_halt(), Ok(vec![]));
+        assert_eq!(*printed.borrow(), vec![3, 5]);
+    }
+
+    #[test]
+    fn test_host_call() {
+        // Opcode 50 stores the sum of squares of its inputs, which is output.
+        let mut t = Interpreter::new("1150,3,4,7,4,7,99,0", &[]);
+        t.register_opcode(
+            50,
+            &[Param::Read, Param::Read, Param::Write],
+            |reads, writes| writes[0] = reads[0] * reads[0] + reads[1] * reads[1],
+        )
+        .unwrap();
+        assert_eq!(t.clone().run_to_halt(), Ok(vec![25]));
+
+        let mut t = Interpreter::new("11150,3,4,7,99", &[]);
+        t.register_opcode(50, &[Param::Read, Param::Read, Param::Write], |_, _| {})
+            .unwrap();
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::ImmediateWrite {
+                insp: 0,
+                opcode: 11150,
+                operand: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut t = Interpreter::new("99", &[]);
+        let noop = |_: &[i64], _: &mut [i64]| {};
+        assert_eq!(
+            t.register_opcode(2, &[], noop),
+            Err(RegisterError::Builtin(2))
+        );
+        assert_eq!(
+            t.register_opcode(99, &[], noop),
+            Err(RegisterError::Builtin(99))
+        );
+        assert_eq!(
+            t.register_opcode(100, &[], noop),
+            Err(RegisterError::InvalidNumber(100))
+        );
+        assert_eq!(
+            t.register_opcode(20, &[Param::Read; 4], noop),
+            Err(RegisterError::TooManyParams(4))
+        );
+        assert_eq!(
+            t.register_opcode(21, &[Param::Write, Param::Write], noop),
+            Err(RegisterError::TooManyWrites(2))
+        );
+        assert_eq!(t.register_opcode(20, &[], noop), Ok(()));
+        assert_eq!(
+            t.register_opcode(20, &[Param::Read], noop),
+            Err(RegisterError::AlreadyRegistered(20))
+        );
+    }
+
+    // Opcode 42 stores 100 at cell 9, then 1 + 1 is stored at cell 10.
+    fn store_100() -> Interpreter {
+        let mut t = Interpreter::new("42,9,1101,1,1,10,99,0,0,0,0", &[]);
+        t.register_opcode(42, &[Param::Write], |_, writes| writes[0] = 100)
+            .unwrap();
+        t
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut t = store_100();
+        t.set_undo_log(Some(10));
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+        assert_eq!(t.step_back(10), 2);
+        assert_eq!(t.insp(), 0);
+        assert_eq!(t.read_memory(9), 0);
+        assert_eq!(t.read_memory(10), 0);
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut t = store_100();
+        t.watch(9..10, WatchKind::Write);
+        assert_eq!(
+            t.run(),
+            Ok(RunOutput::Watch(WatchHit {
+                insp: 0,
+                opcode: 42,
+                access: Access::Write,
+                address: 9,
+                old: 0,
+                new: 100,
+                output: None,
+            }))
+        );
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+    }
+
+    #[test]
+    fn test_coverage_and_profile() {
+        let mut t = store_100();
+        t.start_coverage();
+        t.start_profile();
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+        let coverage = t.take_coverage().unwrap();
+        assert_eq!(coverage.flags(0), "x--");
+        assert_eq!(coverage.flags(9), "--w");
+        assert_eq!(coverage.executed[&0].to_string(), "op42 [9]");
+        assert_eq!(t.profile().unwrap().opcodes.get(&42), Some(&1));
+    }
+
+    #[test]
+    fn test_loop_detection() {
+        // Calls opcode 41 in a loop, which the host may use to make progress.
+        let mut t = Interpreter::new("41,1105,1,0", &[]);
+        t.register_opcode(41, &[], |_, _| {}).unwrap();
+        t.detect_loops(true);
+        assert_eq!(t.run_with_budget(100), Ok(Budgeted::Exhausted));
+    }
+}
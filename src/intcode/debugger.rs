@@ -0,0 +1,450 @@
+use super::{Interpreter, RunOutput, WatchKind};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+
+// Number of instructions that can be stepped back.
+const UNDO_CAPACITY: usize = 1_000_000;
+
+const HELP: &str = "\
+commands:
+  s, step [n]              execute n instructions (default 1)
+  back [n]                 revert n instructions (default 1)
+  c, continue              run until a breakpoint, halt or input request
+  b, break <addr>          break before executing the address
+  b, break op <opcode>     break before executing the opcode (number or mnemonic)
+  d, delete <addr>         remove address breakpoint
+  d, delete op <opcode>    remove opcode breakpoint
+  w, watch <addr>[..<end>] [r|w|rw]
+                           stop after the addresses are read or written (default w)
+  unwatch                  remove all watchpoints
+  i, info                  list breakpoints and watchpoints
+  r, regs                  show insp, relative base and pending input
+  l, list [addr] [n]       disassemble n instructions (default from insp)
+  x <addr> [n]             show n memory cells
+  set <addr> <value>       write value to memory
+  in <value>...            queue input values
+  save <file>              save snapshot of the program state
+  load <file>              restore program state from snapshot
+  h, help                  show this help
+  q, quit                  exit debugger";
+
+// Parse opcode number or mnemonic.
+fn parse_opcode(s: &str) -> Option<i64> {
+    if let Ok(number) = s.parse() {
+        return Some(number);
+    }
+    (1..=99).find(|&number| super::Opcode::new(number).and_then(|o| o.mnemonic()) == Some(s))
+}
+
+// Parse `addr` or `start..end` into an address range.
+fn parse_range(s: &str) -> Result<std::ops::Range<usize>, String> {
+    let invalid = || format!("invalid address range '{}'", s);
+    let mut parts = s.splitn(2, "..");
+    let start: usize = parts.next().unwrap().parse().map_err(|_| invalid())?;
+    let end = match parts.next() {
+        Some(end) => end.parse().map_err(|_| invalid())?,
+        None => start + 1,
+    };
+    Ok(start..end)
+}
+
+fn parse_watch_kind(s: Option<&&str>) -> Result<WatchKind, String> {
+    match s {
+        None | Some(&"w") => Ok(WatchKind::Write),
+        Some(&"r") => Ok(WatchKind::Read),
+        Some(&"rw") => Ok(WatchKind::ReadWrite),
+        Some(s) => Err(format!("invalid watch kind '{}'", s)),
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: Option<&&str>, default: Option<T>) -> Result<T, String> {
+    match arg {
+        Some(s) => s.parse().map_err(|_| format!("invalid argument '{}'", s)),
+        None => default.ok_or_else(|| "missing argument".to_string()),
+    }
+}
+
+// Step debugger around an interpreter. Commands are executed with `command`,
+// which returns the text to show to the user.
+pub struct Debugger {
+    interpreter: Interpreter,
+    breakpoints: BTreeSet<usize>,
+    opcode_breakpoints: BTreeSet<i64>,
+    halted: bool,
+}
+
+impl Debugger {
+    pub fn new(mut interpreter: Interpreter) -> Self {
+        interpreter.set_undo_log(Some(UNDO_CAPACITY));
+        Debugger {
+            interpreter,
+            breakpoints: BTreeSet::new(),
+            opcode_breakpoints: BTreeSet::new(),
+            halted: false,
+        }
+    }
+
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    fn at_breakpoint(&self) -> bool {
+        let insp = self.interpreter.insp();
+        let opcode = self.interpreter.read_memory(insp) % 100;
+        self.breakpoints.contains(&insp) || self.opcode_breakpoints.contains(&opcode)
+    }
+
+    fn current_instruction(&self) -> String {
+        let insp = self.interpreter.insp();
+        match self.interpreter.instruction_at(insp) {
+            Some(instruction) => format!("{:>6}  {}", insp, instruction),
+            None => format!("{:>6}  .data {}", insp, self.interpreter.read_memory(insp)),
+        }
+    }
+
+    // Execute one instruction. Returns false if execution can't continue.
+    fn step(&mut self, out: &mut String) -> bool {
+        if self.halted {
+            writeln!(out, "program has halted").unwrap();
+            return false;
+        }
+
+        let instruction = self.current_instruction();
+        match self.interpreter.step() {
+            Ok(None) => {
+                writeln!(out, "{}", instruction).unwrap();
+                true
+            }
+            Ok(Some(RunOutput::Output(value))) => {
+                writeln!(out, "{}\noutput: {}", instruction, value).unwrap();
+                true
+            }
+            Ok(Some(RunOutput::NeedsInput)) => {
+                writeln!(out, "{}\nwaiting for input", instruction).unwrap();
+                false
+            }
+            Ok(Some(RunOutput::Watch(hit))) => {
+                writeln!(out, "{}", instruction).unwrap();
+                if let Some(value) = hit.output {
+                    writeln!(out, "output: {}", value).unwrap();
+                }
+                writeln!(out, "watchpoint: {}", hit).unwrap();
+                false
+            }
+            Ok(Some(RunOutput::Halt)) => {
+                self.halted = true;
+                writeln!(out, "{}\nprogram halted", instruction).unwrap();
+                false
+            }
+            Err(err) => {
+                writeln!(out, "{}\nerror: {}", instruction, err).unwrap();
+                false
+            }
+        }
+    }
+
+    // Run until breakpoint. Only output and the stopping reason are shown.
+    fn continue_run(&mut self, out: &mut String) {
+        let mut first = true;
+        loop {
+            if !first && self.at_breakpoint() {
+                writeln!(out, "breakpoint\n{}", self.current_instruction()).unwrap();
+                return;
+            }
+            first = false;
+
+            let mut step_out = String::new();
+            let can_continue = self.step(&mut step_out);
+            for line in step_out.lines().skip(1) {
+                writeln!(out, "{}", line).unwrap();
+            }
+            if !can_continue {
+                return;
+            }
+        }
+    }
+
+    fn registers(&self, out: &mut String) {
+        writeln!(out, "insp: {}", self.interpreter.insp()).unwrap();
+        writeln!(out, "relative_base: {}", self.interpreter.relative_base()).unwrap();
+        writeln!(out, "input: {:?}", self.interpreter.pending_input()).unwrap();
+        writeln!(out, "{}", self.current_instruction()).unwrap();
+    }
+
+    // Stops at the end of the address space.
+    fn list(&self, mut address: usize, count: usize, out: &mut String) {
+        for _ in 0..count {
+            let len = match self.interpreter.instruction_at(address) {
+                Some(instruction) => {
+                    writeln!(out, "{:>6}  {}", address, instruction).unwrap();
+                    instruction.params.len() + 1
+                }
+                None => {
+                    let value = self.interpreter.read_memory(address);
+                    writeln!(out, "{:>6}  .data {}", address, value).unwrap();
+                    1
+                }
+            };
+            address = match address.checked_add(len) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+    }
+
+    pub fn command(&mut self, line: &str) -> String {
+        let mut out = String::new();
+        let args: Vec<&str> = line.split_whitespace().collect();
+        if let Err(err) = self.execute(&args, &mut out) {
+            writeln!(out, "{}", err).unwrap();
+        }
+        out
+    }
+
+    fn execute(&mut self, args: &[&str], out: &mut String) -> Result<(), String> {
+        let insp = self.interpreter.insp();
+        match args {
+            [] => {}
+            ["s"] | ["step"] | ["s", _] | ["step", _] => {
+                let count: usize = parse_arg(args.get(1), Some(1))?;
+                for _ in 0..count {
+                    if !self.step(out) {
+                        break;
+                    }
+                }
+            }
+            ["back"] | ["back", _] => {
+                let count: usize = parse_arg(args.get(1), Some(1))?;
+                let reverted = self.interpreter.step_back(count);
+                if reverted > 0 {
+                    self.halted = false;
+                }
+                writeln!(out, "reverted {} instructions", reverted).unwrap();
+                writeln!(out, "{}", self.current_instruction()).unwrap();
+            }
+            ["c"] | ["continue"] => self.continue_run(out),
+            ["b", "op", opcode] | ["break", "op", opcode] => {
+                let opcode = parse_opcode(opcode).ok_or("unknown opcode")?;
+                self.opcode_breakpoints.insert(opcode);
+            }
+            ["b", _] | ["break", _] => {
+                self.breakpoints.insert(parse_arg(args.get(1), None)?);
+            }
+            ["d", "op", opcode] | ["delete", "op", opcode] => {
+                let opcode = parse_opcode(opcode).ok_or("unknown opcode")?;
+                self.opcode_breakpoints.remove(&opcode);
+            }
+            ["d", _] | ["delete", _] => {
+                self.breakpoints.remove(&parse_arg(args.get(1), None)?);
+            }
+            ["i"] | ["info"] => {
+                writeln!(out, "addresses: {:?}", self.breakpoints).unwrap();
+                writeln!(out, "opcodes: {:?}", self.opcode_breakpoints).unwrap();
+                for watchpoint in self.interpreter.watchpoints() {
+                    writeln!(out, "watch: {:?} {:?}", watchpoint.range, watchpoint.kind).unwrap();
+                }
+            }
+            ["w", ..] | ["watch", ..] if args.len() == 2 || args.len() == 3 => {
+                let range = parse_range(args[1])?;
+                let kind = parse_watch_kind(args.get(2))?;
+                self.interpreter.watch(range, kind);
+            }
+            ["unwatch"] => self.interpreter.clear_watchpoints(),
+            ["r"] | ["regs"] => self.registers(out),
+            ["l", ..] | ["list", ..] if args.len() <= 3 => {
+                let address = parse_arg(args.get(1), Some(insp))?;
+                let count = parse_arg(args.get(2), Some(10))?;
+                self.list(address, count, out);
+            }
+            ["x", ..] if args.len() <= 3 => {
+                let address: usize = parse_arg(args.get(1), None)?;
+                let count: usize = parse_arg(args.get(2), Some(1))?;
+                for a in (address..=usize::MAX).take(count) {
+                    writeln!(out, "{:>6}: {}", a, self.interpreter.read_memory(a)).unwrap();
+                }
+            }
+            ["set", _, _] => {
+                let address = parse_arg(args.get(1), None)?;
+                let value = parse_arg(args.get(2), None)?;
+                self.interpreter.write_memory(address, value);
+            }
+            ["in", values @ ..] if !values.is_empty() => {
+                for value in values.iter() {
+                    self.interpreter.put_input(parse_arg(Some(value), None)?);
+                }
+            }
+            ["save", file] => {
+                self.interpreter
+                    .save_snapshot(file)
+                    .map_err(|err| err.to_string())?;
+            }
+            ["load", file] => {
+                // Snapshots don't hold the undo log and watchpoints.
+                let mut interpreter =
+                    Interpreter::load_snapshot(file).map_err(|err| err.to_string())?;
+                interpreter.set_undo_log(Some(UNDO_CAPACITY));
+                for watchpoint in self.interpreter.watchpoints() {
+                    interpreter.watch(watchpoint.range.clone(), watchpoint.kind);
+                }
+                self.interpreter = interpreter;
+                self.halted = false;
+                self.registers(out);
+            }
+            ["h"] | ["help"] => writeln!(out, "{}", HELP).unwrap(),
+            _ => return Err(format!("unknown command '{}', try help", args.join(" "))),
+        }
+        Ok(())
+    }
+
+    // Read commands from stdin until quit or end of input.
+    pub fn repl(&mut self) {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+        println!("{}", self.current_instruction());
+        loop {
+            print!("(icdb) ");
+            io::stdout().flush().unwrap();
+            let line = match lines.next() {
+                Some(line) => line.unwrap(),
+                None => break,
+            };
+            match line.trim() {
+                "q" | "quit" => break,
+                line => print!("{}", self.command(line)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sums inputs until a zero is read.
+    const PROGRAM: &str = "3,15,1006,15,12,1,15,16,16,1105,1,0,4,16,99,0,0";
+
+    #[test]
+    fn test_step_and_registers() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[5]));
+        assert_eq!(debugger.command("step"), "     0  in [15]\n");
+        assert_eq!(debugger.command("x 15"), "    15: 5\n");
+        assert_eq!(
+            debugger.command("regs"),
+            "insp: 2\nrelative_base: 0\ninput: []\n     2  jz [15], #12\n"
+        );
+        assert_eq!(
+            debugger.command("s 4"),
+            "     2  jz [15], #12\n     5  add [15], [16], [16]\n     9  jnz #1, #0\n     0  in [15]\nwaiting for input\n"
+        );
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[5, 6, 0]));
+        debugger.command("break 5");
+        assert_eq!(
+            debugger.command("c"),
+            "breakpoint\n     5  add [15], [16], [16]\n"
+        );
+        assert_eq!(
+            debugger.command("c"),
+            "breakpoint\n     5  add [15], [16], [16]\n"
+        );
+        assert_eq!(debugger.interpreter().read_memory(16), 5);
+
+        debugger.command("delete 5");
+        debugger.command("break op out");
+        assert_eq!(debugger.command("c"), "breakpoint\n    12  out [16]\n");
+        assert_eq!(debugger.command("continue"), "output: 11\nprogram halted\n");
+        assert_eq!(debugger.command("s"), "program has halted\n");
+    }
+
+    #[test]
+    fn test_input_and_memory_edit() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[]));
+        assert_eq!(debugger.command("c"), "waiting for input\n");
+        debugger.command("set 16 100");
+        debugger.command("in 1 0");
+        assert_eq!(debugger.command("c"), "output: 101\nprogram halted\n");
+        assert_eq!(
+            debugger.command("frobnicate"),
+            "unknown command 'frobnicate', try help\n"
+        );
+    }
+
+    #[test]
+    fn test_address_space_end() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[]));
+        assert_eq!(
+            debugger.command("x 18446744073709551615 2"),
+            "18446744073709551615: 0\n"
+        );
+        assert_eq!(
+            debugger.command("l 18446744073709551614 5"),
+            "18446744073709551614  .data 0\n18446744073709551615  .data 0\n"
+        );
+        debugger.command("set 18446744073709551614 4");
+        assert_eq!(
+            debugger.command("l 18446744073709551614 5"),
+            "18446744073709551614  out [0]\n"
+        );
+    }
+
+    #[test]
+    fn test_back() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[5, 0]));
+        debugger.command("break op out");
+        debugger.command("c");
+        assert_eq!(
+            debugger.command("back 2"),
+            "reverted 2 instructions\n     0  in [15]\n"
+        );
+        assert_eq!(debugger.command("regs").lines().nth(2), Some("input: [0]"));
+    }
+
+    #[test]
+    fn test_load_keeps_undo_and_watch() {
+        let path = std::env::temp_dir().join("icdb-test-load.snapshot");
+        let path = path.to_str().unwrap();
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[5, 6, 0]));
+        debugger.command("watch 16");
+        debugger.command(&format!("save {}", path));
+        debugger.command(&format!("load {}", path));
+        debugger.command("s");
+        assert_eq!(
+            debugger.command("back"),
+            "reverted 1 instructions\n     0  in [15]\n"
+        );
+        assert_eq!(
+            debugger.command("c"),
+            "watchpoint: write to 16 by opcode 1 at 5: 0 -> 5\n"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_watch() {
+        let mut debugger = Debugger::new(Interpreter::new(PROGRAM, &[5, 6, 0]));
+        debugger.command("watch 16");
+        assert_eq!(
+            debugger.command("c"),
+            "watchpoint: write to 16 by opcode 1 at 5: 0 -> 5\n"
+        );
+        assert_eq!(
+            debugger.command("c"),
+            "watchpoint: write to 16 by opcode 1 at 5: 5 -> 11\n"
+        );
+        debugger.command("unwatch");
+        debugger.command("w 12..17 r");
+        assert_eq!(
+            debugger.command("c"),
+            "watchpoint: read of 15 by opcode 1006 at 2: 0\n"
+        );
+        assert_eq!(
+            debugger.command("info"),
+            "addresses: {}\nopcodes: {}\nwatch: 12..17 Read\n"
+        );
+        assert_eq!(debugger.command("w 4..x"), "invalid address range '4..x'\n");
+    }
+}
@@ -0,0 +1,825 @@
+use super::cfg::call_return;
+use super::{parse_program, BasicBlock, Cfg, Edge, Instruction, IntcodeError, OpcodeMode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cond {
+    expr: String,
+    negated: bool,
+}
+
+impl Cond {
+    fn negate(&self) -> Cond {
+        Cond {
+            expr: self.expr.clone(),
+            negated: !self.negated,
+        }
+    }
+
+    fn render(&self) -> String {
+        match (self.negated, self.expr.contains(' ')) {
+            (false, _) => self.expr.clone(),
+            (true, false) => format!("!{}", self.expr),
+            (true, true) => format!("!({})", self.expr),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stmt {
+    Line(String),
+    // Start of a block, printed only if a goto jumps to it.
+    Label(usize),
+    Goto(usize),
+    Break,
+    Continue,
+    If(Cond, Vec<Stmt>, Vec<Stmt>),
+    Loop(Vec<Stmt>),
+    While(Cond, Vec<Stmt>),
+}
+
+// How control leaves a block after its statements.
+enum Exit {
+    End,
+    Next(usize),
+    Branch(Cond, usize, usize),
+}
+
+// Function entered at `entry` by calls, or at address 0 for main. Functions
+// that start with `arb #n` have a stack frame of n cells below the relative
+// base, with the return address in the first cell and the arguments after it.
+struct Function {
+    entry: usize,
+    frame: Option<i64>,
+    params: usize,
+    blocks: BTreeSet<usize>,
+}
+
+fn function_name(entry: usize) -> String {
+    match entry {
+        0 => "main".to_string(),
+        _ => format!("f{}", entry),
+    }
+}
+
+fn successors(block: &BasicBlock) -> Vec<usize> {
+    block
+        .edges
+        .iter()
+        .filter_map(|edge| match *edge {
+            Edge::FallThrough(target) | Edge::Jump(target) => Some(target),
+            Edge::Call { ret, .. } => Some(ret),
+            Edge::Unresolved | Edge::InvalidTarget(_) => None,
+        })
+        .collect()
+}
+
+// Offset of the relative parameter the instruction writes to.
+fn relative_write(instruction: &Instruction) -> Option<i64> {
+    let i = instruction.opcode.write_param()?;
+    match instruction.modes()[i] {
+        OpcodeMode::Relative => Some(instruction.params[i]),
+        _ => None,
+    }
+}
+
+// Jump through rb+0, which returns from a function once the frame is released.
+fn is_return(instruction: &Instruction) -> bool {
+    let modes = instruction.modes();
+    let always = match instruction.opcode.number {
+        5 => instruction.params[0] != 0,
+        6 => instruction.params[0] == 0,
+        _ => return false,
+    };
+    always
+        && modes[0] == OpcodeMode::Immediate
+        && modes[1] == OpcodeMode::Relative
+        && instruction.params[1] == 0
+}
+
+struct Decompiler<'a> {
+    cfg: &'a Cfg,
+    functions: BTreeMap<usize, Function>,
+}
+
+// State for decompiling one function.
+struct Emitter<'a> {
+    decompiler: &'a Decompiler<'a>,
+    function: &'a Function,
+    visited: BTreeSet<usize>,
+    // Loop headers with the block the loop exits to.
+    loops: BTreeMap<usize, Option<usize>>,
+    ipdom: BTreeMap<usize, usize>,
+}
+
+impl<'a> Decompiler<'a> {
+    fn new(cfg: &'a Cfg) -> Self {
+        let mut decompiler = Decompiler {
+            cfg,
+            functions: BTreeMap::new(),
+        };
+        let mut entries = BTreeSet::new();
+        entries.insert(0);
+        for block in cfg.blocks.values() {
+            if let Some((target, _)) = call_return(&block.instructions) {
+                entries.insert(target);
+            }
+        }
+        for entry in entries.into_iter().filter(|e| cfg.blocks.contains_key(e)) {
+            let frame = cfg.blocks[&entry]
+                .instructions
+                .first()
+                .filter(|_| entry != 0)
+                .filter(|i| i.opcode.number == 9 && i.modes()[0] == OpcodeMode::Immediate)
+                .map(|i| i.params[0])
+                .filter(|&n| n > 0);
+            let mut blocks = BTreeSet::new();
+            let mut pending = vec![entry];
+            while let Some(start) = pending.pop() {
+                if let Some(block) = cfg.blocks.get(&start) {
+                    if blocks.insert(start) {
+                        pending.extend(successors(block));
+                    }
+                }
+            }
+            let function = Function {
+                entry,
+                frame,
+                params: 0,
+                blocks,
+            };
+            decompiler.functions.insert(entry, function);
+        }
+
+        // Parameter count is the highest argument passed by any call.
+        for block in cfg.blocks.values() {
+            if let Some((target, _)) = call_return(&block.instructions) {
+                let (_, args) = decompiler.split_call(&block.instructions);
+                if let (Some(function), Some(&k)) =
+                    (decompiler.functions.get_mut(&target), args.keys().last())
+                {
+                    function.params = function.params.max(k as usize);
+                }
+            }
+        }
+        decompiler
+    }
+
+    // Split a call block into the instructions before the call and the
+    // arguments stored to rb+1, rb+2, ... right before it.
+    fn split_call<'b>(
+        &self,
+        instructions: &'b [Instruction],
+    ) -> (&'b [Instruction], BTreeMap<i64, &'b Instruction>) {
+        let mut args = BTreeMap::new();
+        let mut end = instructions.len() - 1;
+        while end > 0 {
+            match relative_write(&instructions[end - 1]) {
+                Some(k) if k >= 0 && !args.contains_key(&k) => {
+                    if k > 0 {
+                        args.insert(k, &instructions[end - 1]);
+                    }
+                    end -= 1;
+                }
+                _ => break,
+            }
+        }
+        (&instructions[..end], args)
+    }
+
+    fn operand(&self, function: &Function, param: i64, mode: OpcodeMode) -> String {
+        match (mode, function.frame) {
+            (OpcodeMode::Immediate, _) => param.to_string(),
+            (OpcodeMode::Positional, _) => format!("m{}", param),
+            (OpcodeMode::Relative, _) if param > 0 => format!("tmp{}", param),
+            (OpcodeMode::Relative, Some(frame)) if frame + param >= 0 => {
+                let slot = frame + param;
+                if slot == 0 {
+                    "retaddr".to_string()
+                } else if slot as usize <= function.params {
+                    format!("arg{}", slot)
+                } else {
+                    format!("local{}", slot)
+                }
+            }
+            (OpcodeMode::Relative, _) => format!("rb[{}]", param),
+        }
+    }
+
+    fn operands(&self, function: &Function, instruction: &Instruction) -> Vec<String> {
+        let modes = instruction.modes();
+        instruction
+            .params
+            .iter()
+            .zip(modes.iter())
+            .map(|(&param, &mode)| self.operand(function, param, mode))
+            .collect()
+    }
+
+    // Value computed by an instruction that writes memory.
+    fn value(&self, function: &Function, instruction: &Instruction) -> String {
+        let ops = self.operands(function, instruction);
+        let constants: Vec<Option<i64>> = ops.iter().map(|op| op.parse().ok()).collect();
+        match instruction.opcode.number {
+            1 => match (constants[0], constants[1]) {
+                (Some(a), Some(b)) => a.wrapping_add(b).to_string(),
+                (Some(0), _) => ops[1].clone(),
+                (_, Some(0)) => ops[0].clone(),
+                (_, Some(b)) if b < 0 => format!("{} - {}", ops[0], -(b as i128)),
+                _ => format!("{} + {}", ops[0], ops[1]),
+            },
+            2 => match (constants[0], constants[1]) {
+                (Some(a), Some(b)) => a.wrapping_mul(b).to_string(),
+                (Some(1), _) => ops[1].clone(),
+                (_, Some(1)) => ops[0].clone(),
+                _ => format!("{} * {}", ops[0], ops[1]),
+            },
+            3 => "input()".to_string(),
+            7 => format!("{} < {}", ops[0], ops[1]),
+            _ => format!("{} == {}", ops[0], ops[1]),
+        }
+    }
+
+    fn statement(&self, function: &Function, instruction: &Instruction) -> String {
+        let ops = self.operands(function, instruction);
+        match instruction.opcode.number {
+            1 | 2 | 7 | 8 => format!("{} = {};", ops[2], self.value(function, instruction)),
+            3 => format!("{} = input();", ops[0]),
+            4 => format!("output({});", ops[0]),
+            9 => format!("rb += {};", ops[0]),
+            _ => "halt;".to_string(),
+        }
+    }
+
+    // Statements of a block and how control leaves it.
+    fn block(&self, function: &Function, block: &BasicBlock) -> (Vec<String>, Exit) {
+        let mut instructions = &block.instructions[..];
+        if block.start == function.entry && function.frame.is_some() {
+            instructions = &instructions[1..];
+        }
+
+        if let Some((target, ret)) = call_return(instructions) {
+            let (before, args) = self.split_call(instructions);
+            let mut lines: Vec<String> =
+                before.iter().map(|i| self.statement(function, i)).collect();
+            let count = args.keys().last().cloned().unwrap_or(0);
+            let args: Vec<String> = (1..=count)
+                .map(|k| match args.get(&k) {
+                    Some(i) => self.value(function, i),
+                    None => "?".to_string(),
+                })
+                .collect();
+            lines.push(format!("{}({});", function_name(target), args.join(", ")));
+            return (lines, Exit::Next(ret));
+        }
+
+        let (last, rest) = match instructions.split_last() {
+            Some(split) => split,
+            None => return (vec![], Exit::End),
+        };
+        if is_return(last) {
+            // Releasing the frame is part of the return.
+            let rest = match rest.split_last() {
+                Some((arb, rest)) if arb.opcode.number == 9 => rest,
+                _ => rest,
+            };
+            let mut lines: Vec<String> = rest.iter().map(|i| self.statement(function, i)).collect();
+            lines.push("return;".to_string());
+            return (lines, Exit::End);
+        }
+
+        let jump = matches!(last.opcode.number, 5 | 6);
+        let mut lines: Vec<String> = if jump { rest } else { instructions }
+            .iter()
+            .map(|i| self.statement(function, i))
+            .collect();
+        let target = || self.operand(function, last.params[1], last.modes()[1]);
+        let exit = match block.edges.as_slice() {
+            [Edge::FallThrough(next)] | [Edge::Jump(next)] | [Edge::Call { ret: next, .. }] => {
+                Exit::Next(*next)
+            }
+            [Edge::Unresolved] | [Edge::InvalidTarget(_)] => {
+                lines.push(format!("goto *{};", target()));
+                Exit::End
+            }
+            [first, Edge::FallThrough(not_taken)] => {
+                let mut cond = self.condition(function, rest, last, &mut lines);
+                if last.opcode.number == 6 {
+                    cond = cond.negate();
+                }
+                match first {
+                    Edge::Jump(taken) => Exit::Branch(cond, *taken, *not_taken),
+                    _ => {
+                        lines.push(format!("if ({}) goto *{};", cond.render(), target()));
+                        Exit::Next(*not_taken)
+                    }
+                }
+            }
+            _ => Exit::End,
+        };
+        (lines, exit)
+    }
+
+    // Condition of a jump. A comparison storing the flag right before the jump
+    // is folded into the condition as an assignment.
+    fn condition(
+        &self,
+        function: &Function,
+        rest: &[Instruction],
+        jump: &Instruction,
+        lines: &mut Vec<String>,
+    ) -> Cond {
+        let flag = self.operand(function, jump.params[0], jump.modes()[0]);
+        if let Some(compare) = rest.last() {
+            if matches!(compare.opcode.number, 7 | 8)
+                && jump.modes()[0] != OpcodeMode::Immediate
+                && self.operands(function, compare)[2] == flag
+            {
+                lines.pop();
+                return Cond {
+                    expr: format!("{} = {}", flag, self.value(function, compare)),
+                    negated: false,
+                };
+            }
+        }
+        Cond {
+            expr: flag,
+            negated: false,
+        }
+    }
+
+    fn function(&self, function: &Function) -> String {
+        let mut emitter = Emitter::new(self, function);
+        let mut body = vec![];
+        emitter.sequence(function.entry, None, None, &mut body);
+        let mut gotos = BTreeSet::new();
+        collect_gotos(&body, &mut gotos);
+
+        let params: Vec<String> = (1..=function.params).map(|i| format!("arg{}", i)).collect();
+        let mut s = format!(
+            "fn {}({}) {{\n",
+            function_name(function.entry),
+            params.join(", ")
+        );
+        print(&body, 1, &gotos, &mut s);
+        s.push_str("}\n");
+        s
+    }
+}
+
+impl<'a> Emitter<'a> {
+    fn new(decompiler: &'a Decompiler<'a>, function: &'a Function) -> Self {
+        let mut emitter = Emitter {
+            decompiler,
+            function,
+            visited: BTreeSet::new(),
+            loops: BTreeMap::new(),
+            ipdom: BTreeMap::new(),
+        };
+        emitter.analyze();
+        emitter
+    }
+
+    fn successors(&self, start: usize) -> Vec<usize> {
+        successors(&self.decompiler.cfg.blocks[&start])
+            .into_iter()
+            .filter(|s| self.function.blocks.contains(s))
+            .collect()
+    }
+
+    // Find loops from dominators and if/else joins from post-dominators.
+    fn analyze(&mut self) {
+        let nodes = &self.function.blocks;
+        let mut preds: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for &n in nodes.iter() {
+            for s in self.successors(n) {
+                preds.entry(s).or_default().push(n);
+            }
+        }
+
+        let entry = self.function.entry;
+        let dom = dominators(nodes, entry, |n| preds.get(&n).cloned().unwrap_or_default());
+        let exits: Vec<usize> = nodes
+            .iter()
+            .cloned()
+            .filter(|&n| self.successors(n).is_empty())
+            .collect();
+        let pdom = post_dominators(nodes, &exits, |n| self.successors(n));
+        for &n in nodes.iter() {
+            let strict: BTreeSet<usize> = pdom[&n].iter().cloned().filter(|&p| p != n).collect();
+            if let Some(&p) = strict.iter().find(|p| pdom[p] == strict) {
+                self.ipdom.insert(n, p);
+            }
+        }
+
+        // Natural loops of back edges to blocks that dominate their source.
+        let mut bodies: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for &n in nodes.iter() {
+            for h in self.successors(n) {
+                if !dom[&n].contains(&h) {
+                    continue;
+                }
+                let body = bodies.entry(h).or_insert_with(|| {
+                    let mut body = BTreeSet::new();
+                    body.insert(h);
+                    body
+                });
+                let mut pending = vec![n];
+                while let Some(m) = pending.pop() {
+                    if body.insert(m) {
+                        pending.extend(preds.get(&m).cloned().unwrap_or_default());
+                    }
+                }
+            }
+        }
+        for (h, body) in bodies {
+            let outside = |n: &usize| self.successors(*n).into_iter().find(|s| !body.contains(s));
+            let exit = outside(&h).or_else(|| body.iter().filter_map(outside).min());
+            self.loops.insert(h, exit);
+        }
+    }
+
+    // Emit blocks starting at `start` until reaching `stop`. Inside a loop,
+    // reaching its header or exit continues or breaks the loop.
+    fn sequence(
+        &mut self,
+        start: usize,
+        stop: Option<usize>,
+        inside: Option<(usize, Option<usize>)>,
+        out: &mut Vec<Stmt>,
+    ) {
+        let mut current = start;
+        loop {
+            if Some(current) == stop {
+                return;
+            }
+            if let Some((header, exit)) = inside {
+                if current == header {
+                    out.push(Stmt::Continue);
+                    return;
+                }
+                if Some(current) == exit {
+                    out.push(Stmt::Break);
+                    return;
+                }
+            }
+            if !self.function.blocks.contains(&current) || self.visited.contains(&current) {
+                out.push(Stmt::Goto(current));
+                return;
+            }
+
+            let next = match self.loops.get(&current).cloned() {
+                Some(exit) => {
+                    let mut body = vec![];
+                    let inside = Some((current, exit));
+                    if let Some(next) = self.block(current, None, inside, &mut body) {
+                        self.sequence(next, None, inside, &mut body);
+                    }
+                    structure_loop(body, out);
+                    exit
+                }
+                None => self.block(current, stop, inside, out),
+            };
+            match next {
+                Some(next) => current = next,
+                None => return,
+            }
+        }
+    }
+
+    // Emit one block. Returns the block to continue with.
+    fn block(
+        &mut self,
+        start: usize,
+        stop: Option<usize>,
+        inside: Option<(usize, Option<usize>)>,
+        out: &mut Vec<Stmt>,
+    ) -> Option<usize> {
+        self.visited.insert(start);
+        let (lines, exit) = self
+            .decompiler
+            .block(self.function, &self.decompiler.cfg.blocks[&start]);
+        out.push(Stmt::Label(start));
+        out.extend(lines.into_iter().map(Stmt::Line));
+        match exit {
+            Exit::End => None,
+            Exit::Next(next) => Some(next),
+            Exit::Branch(cond, taken, not_taken) => {
+                // Branches leaving the loop are emitted with their own break
+                // or continue.
+                let join =
+                    self.ipdom.get(&start).cloned().filter(|&join| {
+                        inside.is_none_or(|(h, exit)| join != h && Some(join) != exit)
+                    });
+                let stop = join.or(stop);
+                let mut then = vec![];
+                let mut otherwise = vec![];
+                self.sequence(taken, stop, inside, &mut then);
+                self.sequence(not_taken, stop, inside, &mut otherwise);
+                if then.iter().all(|s| matches!(s, Stmt::Label(_))) {
+                    out.push(Stmt::If(cond.negate(), otherwise, then));
+                } else {
+                    out.push(Stmt::If(cond, then, otherwise));
+                }
+                join
+            }
+        }
+    }
+}
+
+fn dominators<F: Fn(usize) -> Vec<usize>>(
+    nodes: &BTreeSet<usize>,
+    entry: usize,
+    preds: F,
+) -> BTreeMap<usize, BTreeSet<usize>> {
+    post_dominators(nodes, &[entry], preds)
+}
+
+// Dominators of the graph reversed by `next`, with `exits` as entries.
+fn post_dominators<F: Fn(usize) -> Vec<usize>>(
+    nodes: &BTreeSet<usize>,
+    exits: &[usize],
+    next: F,
+) -> BTreeMap<usize, BTreeSet<usize>> {
+    let mut sets: BTreeMap<usize, BTreeSet<usize>> =
+        nodes.iter().map(|&n| (n, nodes.clone())).collect();
+    for &exit in exits {
+        sets.insert(exit, [exit].iter().cloned().collect());
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &n in nodes.iter().filter(|n| !exits.contains(n)) {
+            let mut set: Option<BTreeSet<usize>> = None;
+            for m in next(n) {
+                set = Some(match set {
+                    Some(set) => set.intersection(&sets[&m]).cloned().collect(),
+                    None => sets[&m].clone(),
+                });
+            }
+            let mut set = set.unwrap_or_default();
+            set.insert(n);
+            if set != sets[&n] {
+                sets.insert(n, set);
+                changed = true;
+            }
+        }
+    }
+    sets
+}
+
+// Turn `loop { if (c) break; ... }` into `while (!c) { ... }`.
+fn structure_loop(mut body: Vec<Stmt>, out: &mut Vec<Stmt>) {
+    if body.last() == Some(&Stmt::Continue) {
+        body.pop();
+    }
+    let labels = body
+        .iter()
+        .take_while(|s| matches!(s, Stmt::Label(_)))
+        .count();
+    if let Some(Stmt::If(cond, then, otherwise)) = body.get(labels) {
+        let while_loop = if then.as_slice() == [Stmt::Break] {
+            Some((cond.negate(), otherwise.clone()))
+        } else if otherwise.as_slice() == [Stmt::Break] {
+            Some((cond.clone(), then.clone()))
+        } else {
+            None
+        };
+        if let Some((cond, mut inner)) = while_loop {
+            inner.extend(body.drain(labels + 1..));
+            if inner.last() == Some(&Stmt::Continue) {
+                inner.pop();
+            }
+            out.extend(body.drain(..labels));
+            out.push(Stmt::While(cond, inner));
+            return;
+        }
+    }
+    out.push(Stmt::Loop(body));
+}
+
+fn collect_gotos(stmts: &[Stmt], gotos: &mut BTreeSet<usize>) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::Goto(target) => {
+                gotos.insert(*target);
+            }
+            Stmt::If(_, then, otherwise) => {
+                collect_gotos(then, gotos);
+                collect_gotos(otherwise, gotos);
+            }
+            Stmt::Loop(body) | Stmt::While(_, body) => collect_gotos(body, gotos),
+            _ => {}
+        }
+    }
+}
+
+fn print(stmts: &[Stmt], depth: usize, gotos: &BTreeSet<usize>, s: &mut String) {
+    let indent = "    ".repeat(depth);
+    for stmt in stmts {
+        match stmt {
+            Stmt::Line(line) => writeln!(s, "{}{}", indent, line).unwrap(),
+            Stmt::Label(address) if gotos.contains(address) => {
+                writeln!(s, "{}L{}:", "    ".repeat(depth - 1), address).unwrap()
+            }
+            Stmt::Label(_) => {}
+            Stmt::Goto(address) => writeln!(s, "{}goto L{};", indent, address).unwrap(),
+            Stmt::Break => writeln!(s, "{}break;", indent).unwrap(),
+            Stmt::Continue => writeln!(s, "{}continue;", indent).unwrap(),
+            Stmt::If(cond, then, otherwise) => {
+                writeln!(s, "{}if ({}) {{", indent, cond.render()).unwrap();
+                print(then, depth + 1, gotos, s);
+                let mut otherwise = otherwise;
+                loop {
+                    // Chain `else { if ... }` into `else if`.
+                    let visible: Vec<&Stmt> = otherwise
+                        .iter()
+                        .filter(|s| !matches!(s, Stmt::Label(a) if !gotos.contains(a)))
+                        .collect();
+                    match visible.as_slice() {
+                        [] => break,
+                        [Stmt::If(cond, then, next)] => {
+                            writeln!(s, "{}}} else if ({}) {{", indent, cond.render()).unwrap();
+                            print(then, depth + 1, gotos, s);
+                            otherwise = next;
+                        }
+                        _ => {
+                            writeln!(s, "{}}} else {{", indent).unwrap();
+                            print(otherwise, depth + 1, gotos, s);
+                            break;
+                        }
+                    }
+                }
+                writeln!(s, "{}}}", indent).unwrap();
+            }
+            Stmt::Loop(body) => {
+                writeln!(s, "{}loop {{", indent).unwrap();
+                print(body, depth + 1, gotos, s);
+                writeln!(s, "{}}}", indent).unwrap();
+            }
+            Stmt::While(cond, body) => {
+                writeln!(s, "{}while ({}) {{", indent, cond.render()).unwrap();
+                print(body, depth + 1, gotos, s);
+                writeln!(s, "{}}}", indent).unwrap();
+            }
+        }
+    }
+}
+
+// Pseudo-code of the program with one function for main and for each call
+// target found in the control-flow graph. Memory cells are named by address
+// (`m100`), frame cells by their slot (`arg1`, `local3`) and cells above the
+// relative base, where arguments of calls are stored, as `tmp1`, `tmp2`, ...
+// Unstructured control flow is shown with gotos.
+pub fn decompile_cells(cells: &[i64]) -> String {
+    let cfg = Cfg::build(cells);
+    let decompiler = Decompiler::new(&cfg);
+    decompiler
+        .functions
+        .values()
+        .map(|function| decompiler.function(function))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn decompile(program: &str) -> Result<String, IntcodeError> {
+    Ok(decompile_cells(&parse_program(program)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{assemble, Interpreter};
+
+    // Sums the doubles of 1..n, calling a function for doubling.
+    const SOURCE: &str = "
+                arb #100
+                in [n]
+        loop:   lt [i], [n], [flag]
+                jz [flag], #done
+                add [i], #1, [i]
+                add [i], #0, rb+1
+                add #ret, #0, rb+0
+                jnz #1, #double
+        ret:    add [sum], rb+1, [sum]
+                jnz #1, #loop
+        done:   eq [sum], #0, [flag]
+                jnz [flag], #zero
+                out [sum]
+                hlt
+        zero:   out #-1
+                hlt
+        double: arb #2
+                mul rb-1, #2, rb-1
+                arb #-2
+                jnz #1, rb+0
+        n:      .data 0
+        i:      .data 0
+        sum:    .data 0
+        flag:   .data 0
+    ";
+
+    #[test]
+    fn test_decompile() {
+        let program = assemble(SOURCE).unwrap();
+        assert_eq!(Interpreter::new(&program, &[3]).run_to_halt(), Ok(vec![12]));
+        assert_eq!(
+            decompile(&program).unwrap(),
+            "fn main() {
+    rb += 100;
+    m57 = input();
+    while (m60 = m58 < m57) {
+        m58 = m58 + 1;
+        f46(m58);
+        m59 = m59 + tmp1;
+    }
+    if (m60 = m59 == 0) {
+        output(-1);
+        halt;
+    } else {
+        output(m59);
+        halt;
+    }
+}
+
+fn f46(arg1) {
+    arg1 = arg1 * 2;
+    return;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_else_if() {
+        let source = "
+                in [a]
+                jz [a], #zero
+                lt [a], #0, [flag]
+                jnz [flag], #negative
+                out #1
+                jnz #1, #end
+        zero:   out #0
+                jnz #1, #end
+        negative:
+                out #-1
+        end:    hlt
+        a:      .data 0
+        flag:   .data 0
+        ";
+        assert_eq!(
+            decompile(&assemble(source).unwrap()).unwrap(),
+            "fn main() {
+    m25 = input();
+    if (!m25) {
+        output(0);
+    } else if (m26 = m25 < 0) {
+        output(-1);
+    } else {
+        output(1);
+    }
+    halt;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_goto() {
+        // Irreducible loop between zero and negative.
+        let source = "
+                in [a]
+                jz [a], #zero
+                lt [a], #0, [flag]
+                jnz [flag], #negative
+                out #1
+                hlt
+        zero:   out #0
+        negative:
+                out #-1
+                jnz #1, #zero
+        a:      .data 0
+        flag:   .data 0
+        ";
+        assert_eq!(
+            decompile(&assemble(source).unwrap()).unwrap(),
+            "fn main() {
+    m22 = input();
+    if (!m22) {
+    L15:
+        output(0);
+    L17:
+        output(-1);
+        goto L15;
+    }
+    if (m23 = m22 < 0) {
+        goto L17;
+    }
+    output(1);
+    halt;
+}
+"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Provides values for input instructions once the interpreter's own input
+// queue is empty.
+pub trait InputSource<W = i64> {
+    // Returns None if no input is available yet, which pauses the interpreter
+    // with RunOutput::NeedsInput.
+    fn next_input(&mut self) -> Option<W>;
+}
+
+// Receives values of output instructions instead of them being returned from
+// Interpreter::run.
+pub trait OutputSink<W = i64> {
+    fn output(&mut self, value: W);
+}
+
+impl<W, F: FnMut() -> Option<W>> InputSource<W> for F {
+    fn next_input(&mut self) -> Option<W> {
+        self()
+    }
+}
+
+impl<W, F: FnMut(W)> OutputSink<W> for F {
+    fn output(&mut self, value: W) {
+        self(value)
+    }
+}
+
+// Shared devices can be attached both as input and output of the same
+// interpreter.
+impl<W, T: InputSource<W>> InputSource<W> for Rc<RefCell<T>> {
+    fn next_input(&mut self) -> Option<W> {
+        self.borrow_mut().next_input()
+    }
+}
+
+impl<W, T: OutputSink<W>> OutputSink<W> for Rc<RefCell<T>> {
+    fn output(&mut self, value: W) {
+        self.borrow_mut().output(value)
+    }
+}
@@ -0,0 +1,158 @@
+use super::{parse_program, IntcodeError, Opcode, OpcodeMode};
+use std::fmt;
+
+// Decoded instruction with its raw parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    pub params: Vec<i64>,
+}
+
+impl Instruction {
+    // Address of the instruction following this one.
+    pub fn next_address(&self) -> usize {
+        self.address + self.params.len() + 1
+    }
+
+    pub fn modes(&self) -> [OpcodeMode; 3] {
+        [self.opcode.mode1, self.opcode.mode2, self.opcode.mode3]
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Registered opcodes have no mnemonic.
+        match self.opcode.mnemonic() {
+            Some(mnemonic) => write!(f, "{}", mnemonic)?,
+            None => write!(f, "op{}", self.opcode.number)?,
+        }
+        for (i, (&param, &mode)) in self.params.iter().zip(self.modes().iter()).enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            match mode {
+                OpcodeMode::Positional => write!(f, "{}[{}]", separator, param)?,
+                OpcodeMode::Immediate => write!(f, "{}#{}", separator, param)?,
+                OpcodeMode::Relative if param < 0 => write!(f, "{}rb{}", separator, param)?,
+                OpcodeMode::Relative => write!(f, "{}rb+{}", separator, param)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Decode the instruction at the address. Returns None for unknown opcodes,
+// invalid modes, immediate mode writes and instructions that run past the end
+// of the program.
+pub fn decode(cells: &[i64], address: usize) -> Option<Instruction> {
+    let opcode = Opcode::new(*cells.get(address)?)?;
+    let param_count = opcode.param_count()?;
+    let params = cells.get(address + 1..address + 1 + param_count)?.to_vec();
+    let instruction = Instruction {
+        address,
+        opcode,
+        params,
+    };
+
+    let writes_immediate = opcode
+        .write_param()
+        .is_some_and(|i| instruction.modes()[i] == OpcodeMode::Immediate);
+    if writes_immediate {
+        return None;
+    }
+    Some(instruction)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Instruction(Instruction),
+    Data { address: usize, value: i64 },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match self {
+            Line::Instruction(instruction) => instruction.address,
+            Line::Data { address, .. } => *address,
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Instruction(instruction) => {
+                write!(f, "{:>6}  {}", instruction.address, instruction)
+            }
+            Line::Data { address, value } => write!(f, "{:>6}  .data {}", address, value),
+        }
+    }
+}
+
+// Linear sweep over the memory, cells that don't decode are listed as data.
+pub fn disassemble_cells(cells: &[i64]) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut address = 0;
+    while address < cells.len() {
+        match decode(cells, address) {
+            Some(instruction) => {
+                address = instruction.next_address();
+                lines.push(Line::Instruction(instruction));
+            }
+            None => {
+                lines.push(Line::Data {
+                    address,
+                    value: cells[address],
+                });
+                address += 1;
+            }
+        }
+    }
+    lines
+}
+
+pub fn disassemble(program: &str) -> Result<String, IntcodeError> {
+    Ok(disassemble_cells(&parse_program(program)?)
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let listing =
+            disassemble("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99").unwrap();
+        assert_eq!(
+            listing.lines().collect::<Vec<_>>(),
+            vec![
+                "     0  arb #1",
+                "     2  out rb-1",
+                "     4  add [100], #1, [100]",
+                "     8  eq [100], #16, [101]",
+                "    12  jz [101], #0",
+                "    15  hlt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data() {
+        let lines = disassemble_cells(&[1101, 1, 2, 7, 99, 42, 11101, 3]);
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "     0  add #1, #2, [7]",
+                "     4  hlt",
+                "     5  .data 42",
+                "     6  .data 11101",
+                "     7  .data 3",
+            ]
+        );
+    }
+}
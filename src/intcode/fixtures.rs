@@ -0,0 +1,7 @@
+// Programs shared by the tests of several modules.
+
+// Counts down cell 12 from 3 and outputs each value.
+pub const COUNTDOWN: &str = "4,12,1001,12,-1,12,1005,12,0,99,0,0,3";
+
+// Outputs 1 if the input is 5, otherwise 0.
+pub const IS_FIVE: &str = "3,15,1008,15,5,16,1005,16,12,104,0,99,104,1,99,0,0";
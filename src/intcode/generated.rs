@@ -0,0 +1,6 @@
+// Programs compiled by transpile, run them with Native.
+#[rustfmt::skip]
+pub mod day9;
+#[cfg(test)]
+#[rustfmt::skip]
+pub mod echo;
@@ -0,0 +1,2858 @@
+// Compiled from inputs/day9.txt by the transpile tool, do not edit.
+use crate::intcode::native::{Compiled, Exit};
+use crate::intcode::{IntcodeError, Interpreter};
+
+pub const COMPILED: Compiled = Compiled {
+    len: 973,
+    code: &[(0, 63), (65, 973)],
+    checksum: 0x9bba85872435d349,
+    dispatch,
+};
+
+pub fn dispatch(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    match m.insp {
+        0 => b0(m),
+        11 => b11(m),
+        21 => b21(m),
+        22 => b22(m),
+        23 => b23(m),
+        24 => b24(m),
+        25 => b25(m),
+        27 => b27(m),
+        29 => b29(m),
+        31 => b31(m),
+        34 => b34(m),
+        36 => b36(m),
+        38 => b38(m),
+        41 => b41(m),
+        45 => b45(m),
+        48 => b48(m),
+        50 => b50(m),
+        52 => b52(m),
+        53 => b53(m),
+        55 => b55(m),
+        57 => b57(m),
+        58 => b58(m),
+        60 => b60(m),
+        62 => b62(m),
+        65 => b65(m),
+        194 => b194(m),
+        201 => b201(m),
+        203 => b203(m),
+        216 => b216(m),
+        218 => b218(m),
+        221 => b221(m),
+        225 => b225(m),
+        242 => b242(m),
+        244 => b244(m),
+        251 => b251(m),
+        268 => b268(m),
+        275 => b275(m),
+        277 => b277(m),
+        290 => b290(m),
+        293 => b293(m),
+        295 => b295(m),
+        299 => b299(m),
+        312 => b312(m),
+        315 => b315(m),
+        317 => b317(m),
+        321 => b321(m),
+        338 => b338(m),
+        340 => b340(m),
+        347 => b347(m),
+        356 => b356(m),
+        359 => b359(m),
+        361 => b361(m),
+        365 => b365(m),
+        378 => b378(m),
+        380 => b380(m),
+        387 => b387(m),
+        396 => b396(m),
+        398 => b398(m),
+        401 => b401(m),
+        405 => b405(m),
+        414 => b414(m),
+        421 => b421(m),
+        423 => b423(m),
+        432 => b432(m),
+        434 => b434(m),
+        437 => b437(m),
+        441 => b441(m),
+        454 => b454(m),
+        461 => b461(m),
+        463 => b463(m),
+        476 => b476(m),
+        478 => b478(m),
+        481 => b481(m),
+        485 => b485(m),
+        502 => b502(m),
+        505 => b505(m),
+        507 => b507(m),
+        511 => b511(m),
+        524 => b524(m),
+        526 => b526(m),
+        529 => b529(m),
+        533 => b533(m),
+        542 => b542(m),
+        545 => b545(m),
+        547 => b547(m),
+        551 => b551(m),
+        568 => b568(m),
+        575 => b575(m),
+        577 => b577(m),
+        586 => b586(m),
+        593 => b593(m),
+        595 => b595(m),
+        604 => b604(m),
+        606 => b606(m),
+        609 => b609(m),
+        613 => b613(m),
+        630 => b630(m),
+        632 => b632(m),
+        635 => b635(m),
+        639 => b639(m),
+        652 => b652(m),
+        655 => b655(m),
+        657 => b657(m),
+        661 => b661(m),
+        674 => b674(m),
+        681 => b681(m),
+        683 => b683(m),
+        696 => b696(m),
+        698 => b698(m),
+        701 => b701(m),
+        705 => b705(m),
+        714 => b714(m),
+        716 => b716(m),
+        719 => b719(m),
+        723 => b723(m),
+        740 => b740(m),
+        742 => b742(m),
+        745 => b745(m),
+        749 => b749(m),
+        766 => b766(m),
+        768 => b768(m),
+        771 => b771(m),
+        775 => b775(m),
+        788 => b788(m),
+        790 => b790(m),
+        793 => b793(m),
+        797 => b797(m),
+        814 => b814(m),
+        821 => b821(m),
+        823 => b823(m),
+        840 => b840(m),
+        847 => b847(m),
+        849 => b849(m),
+        866 => b866(m),
+        868 => b868(m),
+        871 => b871(m),
+        875 => b875(m),
+        892 => b892(m),
+        899 => b899(m),
+        901 => b901(m),
+        903 => b903(m),
+        904 => b904(m),
+        915 => b915(m),
+        921 => b921(m),
+        922 => b922(m),
+        931 => b931(m),
+        942 => b942(m),
+        957 => b957(m),
+        964 => b964(m),
+        968 => b968(m),
+        _ => Ok(Exit::Fallback),
+    }
+}
+
+fn is_code(address: usize) -> bool {
+    matches!(
+        address,
+        0..=62
+        | 65..=972
+    )
+}
+
+fn b0(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 0: mul #34463338, #34463338, [63]
+    m.insp = 0;
+    let a: i64 = 34463338;
+    let b: i64 = 34463338;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 4;
+        return Ok(Exit::Fallback);
+    }
+    // 4: lt [63], #34463338, [63]
+    m.insp = 4;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 34463338;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 8;
+        return Ok(Exit::Fallback);
+    }
+    // 8: jnz [63], #53
+    m.insp = 8;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&53)?
+    } else {
+        11
+    };
+    Ok(Exit::Continue)
+}
+
+fn b11(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 11: add #0, #3, [1000]
+    m.insp = 11;
+    let a: i64 = 0;
+    let b: i64 = 3;
+    if is_code(m.save(1000, m.add(&a, &b)?)?) {
+        m.insp = 15;
+        return Ok(Exit::Fallback);
+    }
+    // 15: arb #988
+    m.insp = 15;
+    let a: i64 = 988;
+    m.adjust_relative_base(&a)?;
+    // 17: arb rb+12
+    m.insp = 17;
+    let a: i64 = m.load(m.relative_address(&12)?)?;
+    m.adjust_relative_base(&a)?;
+    // 19: arb [1000]
+    m.insp = 19;
+    let a: i64 = m.load(1000)?;
+    m.adjust_relative_base(&a)?;
+    m.insp = 21;
+    Ok(Exit::Continue)
+}
+
+fn b21(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 21: arb rb+6
+    m.insp = 21;
+    let a: i64 = m.load(m.relative_address(&6)?)?;
+    m.adjust_relative_base(&a)?;
+    m.insp = 23;
+    Ok(Exit::Continue)
+}
+
+fn b22(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 22: jz [209], [3]
+    m.insp = 22;
+    let a: i64 = m.load(209)?;
+    m.insp = if a == 0 {
+        m.jump_target(&m.load(3)?)?
+    } else {
+        25
+    };
+    Ok(Exit::Continue)
+}
+
+fn b23(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 23: arb rb+3
+    m.insp = 23;
+    let a: i64 = m.load(m.relative_address(&3)?)?;
+    m.adjust_relative_base(&a)?;
+    m.insp = 25;
+    Ok(Exit::Continue)
+}
+
+fn b24(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 24: in [203]
+    m.insp = 24;
+    let value = match m.peek_input() {
+        Some(value) => value,
+        None => return Ok(Exit::NeedsInput),
+    };
+    let address = m.save(203, value)?;
+    m.consume_input();
+    if is_code(address) {
+        m.insp = 26;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 26;
+    Ok(Exit::Continue)
+}
+
+fn b25(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 25: in rb+0
+    m.insp = 25;
+    let value = match m.peek_input() {
+        Some(value) => value,
+        None => return Ok(Exit::NeedsInput),
+    };
+    let address = m.save(m.relative_base, value)?;
+    m.consume_input();
+    if is_code(address) {
+        m.insp = 27;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 27;
+    Ok(Exit::Continue)
+}
+
+fn b27(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 27: eq [1000], #1, [63]
+    m.insp = 27;
+    let a: i64 = m.load(1000)?;
+    let b: i64 = 1;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 31;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 31;
+    Ok(Exit::Continue)
+}
+
+fn b29(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 29: add [63], [1005], [63]
+    m.insp = 29;
+    let a: i64 = m.load(63)?;
+    let b: i64 = m.load(1005)?;
+    if is_code(m.save(63, m.add(&a, &b)?)?) {
+        m.insp = 33;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 33;
+    Ok(Exit::Continue)
+}
+
+fn b31(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 31: jnz [63], #65
+    m.insp = 31;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&65)?
+    } else {
+        34
+    };
+    Ok(Exit::Continue)
+}
+
+fn b34(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 34: eq [1000], #2, [63]
+    m.insp = 34;
+    let a: i64 = m.load(1000)?;
+    let b: i64 = 2;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 38;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 38;
+    Ok(Exit::Continue)
+}
+
+fn b36(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 36: mul [63], [1005], [63]
+    m.insp = 36;
+    let a: i64 = m.load(63)?;
+    let b: i64 = m.load(1005)?;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 40;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 40;
+    Ok(Exit::Continue)
+}
+
+fn b38(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 38: jnz [63], #904
+    m.insp = 38;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&904)?
+    } else {
+        41
+    };
+    Ok(Exit::Continue)
+}
+
+fn b41(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 41: eq [1000], #0, [63]
+    m.insp = 41;
+    let a: i64 = m.load(1000)?;
+    let b: i64 = 0;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 45;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 45;
+    Ok(Exit::Continue)
+}
+
+fn b45(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 45: jnz [63], #58
+    m.insp = 45;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&58)?
+    } else {
+        48
+    };
+    Ok(Exit::Continue)
+}
+
+fn b48(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 48: out [25]
+    m.insp = 48;
+    let a: i64 = m.load(25)?;
+    m.insp = 50;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b50(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 50: out #0
+    m.insp = 50;
+    let a: i64 = 0;
+    m.insp = 52;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b52(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 52: hlt
+    m.insp = 52;
+    m.halt();
+    Ok(Exit::Halt)
+}
+
+fn b53(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 53: out [0]
+    m.insp = 53;
+    let a: i64 = m.load(0)?;
+    m.insp = 55;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b55(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 55: out #0
+    m.insp = 55;
+    let a: i64 = 0;
+    m.insp = 57;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b57(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 57: hlt
+    m.insp = 57;
+    m.halt();
+    Ok(Exit::Halt)
+}
+
+fn b58(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 58: out [17]
+    m.insp = 58;
+    let a: i64 = m.load(17)?;
+    m.insp = 60;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b60(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 60: out #0
+    m.insp = 60;
+    let a: i64 = 0;
+    m.insp = 62;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b62(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 62: hlt
+    m.insp = 62;
+    m.halt();
+    Ok(Exit::Halt)
+}
+
+fn b65(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 65: add #0, #396, [1029]
+    m.insp = 65;
+    let a: i64 = 0;
+    let b: i64 = 396;
+    if is_code(m.save(1029, m.add(&a, &b)?)?) {
+        m.insp = 69;
+        return Ok(Exit::Fallback);
+    }
+    // 69: add #0, #356, [1023]
+    m.insp = 69;
+    let a: i64 = 0;
+    let b: i64 = 356;
+    if is_code(m.save(1023, m.add(&a, &b)?)?) {
+        m.insp = 73;
+        return Ok(Exit::Fallback);
+    }
+    // 73: add #401, #0, [1028]
+    m.insp = 73;
+    let a: i64 = 401;
+    let b: i64 = 0;
+    if is_code(m.save(1028, m.add(&a, &b)?)?) {
+        m.insp = 77;
+        return Ok(Exit::Fallback);
+    }
+    // 77: add #24, #0, [1008]
+    m.insp = 77;
+    let a: i64 = 24;
+    let b: i64 = 0;
+    if is_code(m.save(1008, m.add(&a, &b)?)?) {
+        m.insp = 81;
+        return Ok(Exit::Fallback);
+    }
+    // 81: add #33, #0, [1019]
+    m.insp = 81;
+    let a: i64 = 33;
+    let b: i64 = 0;
+    if is_code(m.save(1019, m.add(&a, &b)?)?) {
+        m.insp = 85;
+        return Ok(Exit::Fallback);
+    }
+    // 85: add #35, #0, [1010]
+    m.insp = 85;
+    let a: i64 = 35;
+    let b: i64 = 0;
+    if is_code(m.save(1010, m.add(&a, &b)?)?) {
+        m.insp = 89;
+        return Ok(Exit::Fallback);
+    }
+    // 89: mul #359, #1, [1022]
+    m.insp = 89;
+    let a: i64 = 359;
+    let b: i64 = 1;
+    if is_code(m.save(1022, m.mul(&a, &b)?)?) {
+        m.insp = 93;
+        return Ok(Exit::Fallback);
+    }
+    // 93: mul #32, #1, [1001]
+    m.insp = 93;
+    let a: i64 = 32;
+    let b: i64 = 1;
+    if is_code(m.save(1001, m.mul(&a, &b)?)?) {
+        m.insp = 97;
+        return Ok(Exit::Fallback);
+    }
+    // 97: add #37, #0, [1004]
+    m.insp = 97;
+    let a: i64 = 37;
+    let b: i64 = 0;
+    if is_code(m.save(1004, m.add(&a, &b)?)?) {
+        m.insp = 101;
+        return Ok(Exit::Fallback);
+    }
+    // 101: add #0, #31, [1009]
+    m.insp = 101;
+    let a: i64 = 0;
+    let b: i64 = 31;
+    if is_code(m.save(1009, m.add(&a, &b)?)?) {
+        m.insp = 105;
+        return Ok(Exit::Fallback);
+    }
+    // 105: add #0, #30, [1003]
+    m.insp = 105;
+    let a: i64 = 0;
+    let b: i64 = 30;
+    if is_code(m.save(1003, m.add(&a, &b)?)?) {
+        m.insp = 109;
+        return Ok(Exit::Fallback);
+    }
+    // 109: add #28, #0, [1002]
+    m.insp = 109;
+    let a: i64 = 28;
+    let b: i64 = 0;
+    if is_code(m.save(1002, m.add(&a, &b)?)?) {
+        m.insp = 113;
+        return Ok(Exit::Fallback);
+    }
+    // 113: mul #1, #36, [1014]
+    m.insp = 113;
+    let a: i64 = 1;
+    let b: i64 = 36;
+    if is_code(m.save(1014, m.mul(&a, &b)?)?) {
+        m.insp = 117;
+        return Ok(Exit::Fallback);
+    }
+    // 117: mul #20, #1, [1012]
+    m.insp = 117;
+    let a: i64 = 20;
+    let b: i64 = 1;
+    if is_code(m.save(1012, m.mul(&a, &b)?)?) {
+        m.insp = 121;
+        return Ok(Exit::Fallback);
+    }
+    // 121: add #21, #0, [1000]
+    m.insp = 121;
+    let a: i64 = 21;
+    let b: i64 = 0;
+    if is_code(m.save(1000, m.add(&a, &b)?)?) {
+        m.insp = 125;
+        return Ok(Exit::Fallback);
+    }
+    // 125: add #0, #22, [1015]
+    m.insp = 125;
+    let a: i64 = 0;
+    let b: i64 = 22;
+    if is_code(m.save(1015, m.add(&a, &b)?)?) {
+        m.insp = 129;
+        return Ok(Exit::Fallback);
+    }
+    // 129: mul #23, #1, [1013]
+    m.insp = 129;
+    let a: i64 = 23;
+    let b: i64 = 1;
+    if is_code(m.save(1013, m.mul(&a, &b)?)?) {
+        m.insp = 133;
+        return Ok(Exit::Fallback);
+    }
+    // 133: mul #1, #1, [1021]
+    m.insp = 133;
+    let a: i64 = 1;
+    let b: i64 = 1;
+    if is_code(m.save(1021, m.mul(&a, &b)?)?) {
+        m.insp = 137;
+        return Ok(Exit::Fallback);
+    }
+    // 137: mul #1, #39, [1007]
+    m.insp = 137;
+    let a: i64 = 1;
+    let b: i64 = 39;
+    if is_code(m.save(1007, m.mul(&a, &b)?)?) {
+        m.insp = 141;
+        return Ok(Exit::Fallback);
+    }
+    // 141: mul #26, #1, [1017]
+    m.insp = 141;
+    let a: i64 = 26;
+    let b: i64 = 1;
+    if is_code(m.save(1017, m.mul(&a, &b)?)?) {
+        m.insp = 145;
+        return Ok(Exit::Fallback);
+    }
+    // 145: add #0, #38, [1016]
+    m.insp = 145;
+    let a: i64 = 0;
+    let b: i64 = 38;
+    if is_code(m.save(1016, m.add(&a, &b)?)?) {
+        m.insp = 149;
+        return Ok(Exit::Fallback);
+    }
+    // 149: add #0, #437, [1024]
+    m.insp = 149;
+    let a: i64 = 0;
+    let b: i64 = 437;
+    if is_code(m.save(1024, m.add(&a, &b)?)?) {
+        m.insp = 153;
+        return Ok(Exit::Fallback);
+    }
+    // 153: mul #432, #1, [1025]
+    m.insp = 153;
+    let a: i64 = 432;
+    let b: i64 = 1;
+    if is_code(m.save(1025, m.mul(&a, &b)?)?) {
+        m.insp = 157;
+        return Ok(Exit::Fallback);
+    }
+    // 157: add #0, #421, [1026]
+    m.insp = 157;
+    let a: i64 = 0;
+    let b: i64 = 421;
+    if is_code(m.save(1026, m.add(&a, &b)?)?) {
+        m.insp = 161;
+        return Ok(Exit::Fallback);
+    }
+    // 161: add #0, #29, [1005]
+    m.insp = 161;
+    let a: i64 = 0;
+    let b: i64 = 29;
+    if is_code(m.save(1005, m.add(&a, &b)?)?) {
+        m.insp = 165;
+        return Ok(Exit::Fallback);
+    }
+    // 165: add #27, #0, [1011]
+    m.insp = 165;
+    let a: i64 = 27;
+    let b: i64 = 0;
+    if is_code(m.save(1011, m.add(&a, &b)?)?) {
+        m.insp = 169;
+        return Ok(Exit::Fallback);
+    }
+    // 169: mul #1, #0, [1020]
+    m.insp = 169;
+    let a: i64 = 1;
+    let b: i64 = 0;
+    if is_code(m.save(1020, m.mul(&a, &b)?)?) {
+        m.insp = 173;
+        return Ok(Exit::Fallback);
+    }
+    // 173: add #0, #25, [1018]
+    m.insp = 173;
+    let a: i64 = 0;
+    let b: i64 = 25;
+    if is_code(m.save(1018, m.add(&a, &b)?)?) {
+        m.insp = 177;
+        return Ok(Exit::Fallback);
+    }
+    // 177: add #0, #414, [1027]
+    m.insp = 177;
+    let a: i64 = 0;
+    let b: i64 = 414;
+    if is_code(m.save(1027, m.add(&a, &b)?)?) {
+        m.insp = 181;
+        return Ok(Exit::Fallback);
+    }
+    // 181: mul #34, #1, [1006]
+    m.insp = 181;
+    let a: i64 = 34;
+    let b: i64 = 1;
+    if is_code(m.save(1006, m.mul(&a, &b)?)?) {
+        m.insp = 185;
+        return Ok(Exit::Fallback);
+    }
+    // 185: arb #6
+    m.insp = 185;
+    let a: i64 = 6;
+    m.adjust_relative_base(&a)?;
+    // 187: eq #33, rb-3, [63]
+    m.insp = 187;
+    let a: i64 = 33;
+    let b: i64 = m.load(m.relative_address(&-3)?)?;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 191;
+        return Ok(Exit::Fallback);
+    }
+    // 191: jnz [63], #201
+    m.insp = 191;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&201)?
+    } else {
+        194
+    };
+    Ok(Exit::Continue)
+}
+
+fn b194(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 194: add [64], #1, [64]
+    m.insp = 194;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 198;
+        return Ok(Exit::Fallback);
+    }
+    // 198: jnz #1, #203
+    m.insp = 198;
+    m.insp = m.jump_target(&203)?;
+    Ok(Exit::Continue)
+}
+
+fn b201(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 201: out [187]
+    m.insp = 201;
+    let a: i64 = m.load(187)?;
+    m.insp = 203;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b203(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 203: mul [64], #2, [64]
+    m.insp = 203;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 207;
+        return Ok(Exit::Fallback);
+    }
+    // 207: arb #14
+    m.insp = 207;
+    let a: i64 = 14;
+    m.adjust_relative_base(&a)?;
+    // 209: eq #40, #40, rb-6
+    m.insp = 209;
+    let a: i64 = 40;
+    let b: i64 = 40;
+    if is_code(m.save(m.relative_address(&-6)?, i64::from(a == b))?) {
+        m.insp = 213;
+        return Ok(Exit::Fallback);
+    }
+    // 213: jnz [1014], #221
+    m.insp = 213;
+    let a: i64 = m.load(1014)?;
+    m.insp = if a != 0 {
+        m.jump_target(&221)?
+    } else {
+        216
+    };
+    Ok(Exit::Continue)
+}
+
+fn b216(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 216: out [209]
+    m.insp = 216;
+    let a: i64 = m.load(209)?;
+    m.insp = 218;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b218(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 218: jnz #1, #225
+    m.insp = 218;
+    m.insp = m.jump_target(&225)?;
+    Ok(Exit::Continue)
+}
+
+fn b221(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 221: add [64], #1, [64]
+    m.insp = 221;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 225;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 225;
+    Ok(Exit::Continue)
+}
+
+fn b225(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 225: mul [64], #2, [64]
+    m.insp = 225;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 229;
+        return Ok(Exit::Fallback);
+    }
+    // 229: arb #-21
+    m.insp = 229;
+    let a: i64 = -21;
+    m.adjust_relative_base(&a)?;
+    // 231: mul #1, rb+3, [63]
+    m.insp = 231;
+    let a: i64 = 1;
+    let b: i64 = m.load(m.relative_address(&3)?)?;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 235;
+        return Ok(Exit::Fallback);
+    }
+    // 235: eq [63], #28, [63]
+    m.insp = 235;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 28;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 239;
+        return Ok(Exit::Fallback);
+    }
+    // 239: jnz [63], #251
+    m.insp = 239;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&251)?
+    } else {
+        242
+    };
+    Ok(Exit::Continue)
+}
+
+fn b242(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 242: out [231]
+    m.insp = 242;
+    let a: i64 = m.load(231)?;
+    m.insp = 244;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b244(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 244: add [64], #1, [64]
+    m.insp = 244;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 248;
+        return Ok(Exit::Fallback);
+    }
+    // 248: jz #0, #251
+    m.insp = 248;
+    m.insp = m.jump_target(&251)?;
+    Ok(Exit::Continue)
+}
+
+fn b251(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 251: mul [64], #2, [64]
+    m.insp = 251;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 255;
+        return Ok(Exit::Fallback);
+    }
+    // 255: arb #12
+    m.insp = 255;
+    let a: i64 = 12;
+    m.adjust_relative_base(&a)?;
+    // 257: add #0, rb-3, [63]
+    m.insp = 257;
+    let a: i64 = 0;
+    let b: i64 = m.load(m.relative_address(&-3)?)?;
+    if is_code(m.save(63, m.add(&a, &b)?)?) {
+        m.insp = 261;
+        return Ok(Exit::Fallback);
+    }
+    // 261: eq [63], #21, [63]
+    m.insp = 261;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 21;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 265;
+        return Ok(Exit::Fallback);
+    }
+    // 265: jnz [63], #275
+    m.insp = 265;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&275)?
+    } else {
+        268
+    };
+    Ok(Exit::Continue)
+}
+
+fn b268(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 268: add [64], #1, [64]
+    m.insp = 268;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 272;
+        return Ok(Exit::Fallback);
+    }
+    // 272: jnz #1, #277
+    m.insp = 272;
+    m.insp = m.jump_target(&277)?;
+    Ok(Exit::Continue)
+}
+
+fn b275(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 275: out [257]
+    m.insp = 275;
+    let a: i64 = m.load(257)?;
+    m.insp = 277;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b277(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 277: mul [64], #2, [64]
+    m.insp = 277;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 281;
+        return Ok(Exit::Fallback);
+    }
+    // 281: arb #-10
+    m.insp = 281;
+    let a: i64 = -10;
+    m.adjust_relative_base(&a)?;
+    // 283: lt rb+1, #27, [63]
+    m.insp = 283;
+    let a: i64 = m.load(m.relative_address(&1)?)?;
+    let b: i64 = 27;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 287;
+        return Ok(Exit::Fallback);
+    }
+    // 287: jnz [63], #293
+    m.insp = 287;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&293)?
+    } else {
+        290
+    };
+    Ok(Exit::Continue)
+}
+
+fn b290(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 290: jnz #1, #299
+    m.insp = 290;
+    m.insp = m.jump_target(&299)?;
+    Ok(Exit::Continue)
+}
+
+fn b293(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 293: out [283]
+    m.insp = 293;
+    let a: i64 = m.load(283)?;
+    m.insp = 295;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b295(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 295: add [64], #1, [64]
+    m.insp = 295;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 299;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 299;
+    Ok(Exit::Continue)
+}
+
+fn b299(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 299: mul [64], #2, [64]
+    m.insp = 299;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 303;
+        return Ok(Exit::Fallback);
+    }
+    // 303: arb #9
+    m.insp = 303;
+    let a: i64 = 9;
+    m.adjust_relative_base(&a)?;
+    // 305: eq #41, #42, rb+3
+    m.insp = 305;
+    let a: i64 = 41;
+    let b: i64 = 42;
+    if is_code(m.save(m.relative_address(&3)?, i64::from(a == b))?) {
+        m.insp = 309;
+        return Ok(Exit::Fallback);
+    }
+    // 309: jnz [1013], #315
+    m.insp = 309;
+    let a: i64 = m.load(1013)?;
+    m.insp = if a != 0 {
+        m.jump_target(&315)?
+    } else {
+        312
+    };
+    Ok(Exit::Continue)
+}
+
+fn b312(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 312: jnz #1, #321
+    m.insp = 312;
+    m.insp = m.jump_target(&321)?;
+    Ok(Exit::Continue)
+}
+
+fn b315(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 315: out [305]
+    m.insp = 315;
+    let a: i64 = m.load(305)?;
+    m.insp = 317;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b317(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 317: add [64], #1, [64]
+    m.insp = 317;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 321;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 321;
+    Ok(Exit::Continue)
+}
+
+fn b321(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 321: mul [64], #2, [64]
+    m.insp = 321;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 325;
+        return Ok(Exit::Fallback);
+    }
+    // 325: arb #-12
+    m.insp = 325;
+    let a: i64 = -12;
+    m.adjust_relative_base(&a)?;
+    // 327: mul rb+6, #1, [63]
+    m.insp = 327;
+    let a: i64 = m.load(m.relative_address(&6)?)?;
+    let b: i64 = 1;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 331;
+        return Ok(Exit::Fallback);
+    }
+    // 331: eq [63], #37, [63]
+    m.insp = 331;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 37;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 335;
+        return Ok(Exit::Fallback);
+    }
+    // 335: jnz [63], #347
+    m.insp = 335;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&347)?
+    } else {
+        338
+    };
+    Ok(Exit::Continue)
+}
+
+fn b338(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 338: out [327]
+    m.insp = 338;
+    let a: i64 = m.load(327)?;
+    m.insp = 340;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b340(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 340: add [64], #1, [64]
+    m.insp = 340;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 344;
+        return Ok(Exit::Fallback);
+    }
+    // 344: jnz #1, #347
+    m.insp = 344;
+    m.insp = m.jump_target(&347)?;
+    Ok(Exit::Continue)
+}
+
+fn b347(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 347: mul [64], #2, [64]
+    m.insp = 347;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 351;
+        return Ok(Exit::Fallback);
+    }
+    // 351: arb #29
+    m.insp = 351;
+    let a: i64 = 29;
+    m.adjust_relative_base(&a)?;
+    // 353: jnz #1, rb-4
+    m.insp = 353;
+    m.insp = m.jump_target(&m.load(m.relative_address(&-4)?)?)?;
+    Ok(Exit::Continue)
+}
+
+fn b356(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 356: jnz #1, #365
+    m.insp = 356;
+    m.insp = m.jump_target(&365)?;
+    Ok(Exit::Continue)
+}
+
+fn b359(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 359: out [353]
+    m.insp = 359;
+    let a: i64 = m.load(353)?;
+    m.insp = 361;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b361(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 361: add [64], #1, [64]
+    m.insp = 361;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 365;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 365;
+    Ok(Exit::Continue)
+}
+
+fn b365(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 365: mul [64], #2, [64]
+    m.insp = 365;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 369;
+        return Ok(Exit::Fallback);
+    }
+    // 369: arb #-17
+    m.insp = 369;
+    let a: i64 = -17;
+    m.adjust_relative_base(&a)?;
+    // 371: eq #32, rb-9, [63]
+    m.insp = 371;
+    let a: i64 = 32;
+    let b: i64 = m.load(m.relative_address(&-9)?)?;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 375;
+        return Ok(Exit::Fallback);
+    }
+    // 375: jnz [63], #387
+    m.insp = 375;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&387)?
+    } else {
+        378
+    };
+    Ok(Exit::Continue)
+}
+
+fn b378(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 378: out [371]
+    m.insp = 378;
+    let a: i64 = m.load(371)?;
+    m.insp = 380;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b380(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 380: add [64], #1, [64]
+    m.insp = 380;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 384;
+        return Ok(Exit::Fallback);
+    }
+    // 384: jnz #1, #387
+    m.insp = 384;
+    m.insp = m.jump_target(&387)?;
+    Ok(Exit::Continue)
+}
+
+fn b387(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 387: mul [64], #2, [64]
+    m.insp = 387;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 391;
+        return Ok(Exit::Fallback);
+    }
+    // 391: arb #17
+    m.insp = 391;
+    let a: i64 = 17;
+    m.adjust_relative_base(&a)?;
+    // 393: jz #0, rb+1
+    m.insp = 393;
+    m.insp = m.jump_target(&m.load(m.relative_address(&1)?)?)?;
+    Ok(Exit::Continue)
+}
+
+fn b396(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 396: out [393]
+    m.insp = 396;
+    let a: i64 = m.load(393)?;
+    m.insp = 398;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b398(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 398: jnz #1, #405
+    m.insp = 398;
+    m.insp = m.jump_target(&405)?;
+    Ok(Exit::Continue)
+}
+
+fn b401(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 401: add [64], #1, [64]
+    m.insp = 401;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 405;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 405;
+    Ok(Exit::Continue)
+}
+
+fn b405(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 405: mul [64], #2, [64]
+    m.insp = 405;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 409;
+        return Ok(Exit::Fallback);
+    }
+    // 409: arb #1
+    m.insp = 409;
+    let a: i64 = 1;
+    m.adjust_relative_base(&a)?;
+    // 411: jz #0, rb-1
+    m.insp = 411;
+    m.insp = m.jump_target(&m.load(m.relative_address(&-1)?)?)?;
+    Ok(Exit::Continue)
+}
+
+fn b414(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 414: add [64], #1, [64]
+    m.insp = 414;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 418;
+        return Ok(Exit::Fallback);
+    }
+    // 418: jz #0, #423
+    m.insp = 418;
+    m.insp = m.jump_target(&423)?;
+    Ok(Exit::Continue)
+}
+
+fn b421(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 421: out [411]
+    m.insp = 421;
+    let a: i64 = m.load(411)?;
+    m.insp = 423;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b423(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 423: mul [64], #2, [64]
+    m.insp = 423;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 427;
+        return Ok(Exit::Fallback);
+    }
+    // 427: arb #-13
+    m.insp = 427;
+    let a: i64 = -13;
+    m.adjust_relative_base(&a)?;
+    // 429: jnz #1, rb+9
+    m.insp = 429;
+    m.insp = m.jump_target(&m.load(m.relative_address(&9)?)?)?;
+    Ok(Exit::Continue)
+}
+
+fn b432(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 432: out [429]
+    m.insp = 432;
+    let a: i64 = m.load(429)?;
+    m.insp = 434;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b434(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 434: jz #0, #441
+    m.insp = 434;
+    m.insp = m.jump_target(&441)?;
+    Ok(Exit::Continue)
+}
+
+fn b437(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 437: add [64], #1, [64]
+    m.insp = 437;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 441;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 441;
+    Ok(Exit::Continue)
+}
+
+fn b441(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 441: mul [64], #2, [64]
+    m.insp = 441;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 445;
+        return Ok(Exit::Fallback);
+    }
+    // 445: arb #3
+    m.insp = 445;
+    let a: i64 = 3;
+    m.adjust_relative_base(&a)?;
+    // 447: lt #42, #41, rb-1
+    m.insp = 447;
+    let a: i64 = 42;
+    let b: i64 = 41;
+    if is_code(m.save(m.relative_address(&-1)?, i64::from(a < b))?) {
+        m.insp = 451;
+        return Ok(Exit::Fallback);
+    }
+    // 451: jnz [1017], #461
+    m.insp = 451;
+    let a: i64 = m.load(1017)?;
+    m.insp = if a != 0 {
+        m.jump_target(&461)?
+    } else {
+        454
+    };
+    Ok(Exit::Continue)
+}
+
+fn b454(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 454: add [64], #1, [64]
+    m.insp = 454;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 458;
+        return Ok(Exit::Fallback);
+    }
+    // 458: jz #0, #463
+    m.insp = 458;
+    m.insp = m.jump_target(&463)?;
+    Ok(Exit::Continue)
+}
+
+fn b461(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 461: out [447]
+    m.insp = 461;
+    let a: i64 = m.load(447)?;
+    m.insp = 463;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b463(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 463: mul [64], #2, [64]
+    m.insp = 463;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 467;
+        return Ok(Exit::Fallback);
+    }
+    // 467: arb #-4
+    m.insp = 467;
+    let a: i64 = -4;
+    m.adjust_relative_base(&a)?;
+    // 469: lt #43, #44, rb+1
+    m.insp = 469;
+    let a: i64 = 43;
+    let b: i64 = 44;
+    if is_code(m.save(m.relative_address(&1)?, i64::from(a < b))?) {
+        m.insp = 473;
+        return Ok(Exit::Fallback);
+    }
+    // 473: jnz [1015], #481
+    m.insp = 473;
+    let a: i64 = m.load(1015)?;
+    m.insp = if a != 0 {
+        m.jump_target(&481)?
+    } else {
+        476
+    };
+    Ok(Exit::Continue)
+}
+
+fn b476(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 476: out [469]
+    m.insp = 476;
+    let a: i64 = m.load(469)?;
+    m.insp = 478;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b478(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 478: jz #0, #485
+    m.insp = 478;
+    m.insp = m.jump_target(&485)?;
+    Ok(Exit::Continue)
+}
+
+fn b481(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 481: add [64], #1, [64]
+    m.insp = 481;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 485;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 485;
+    Ok(Exit::Continue)
+}
+
+fn b485(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 485: mul [64], #2, [64]
+    m.insp = 485;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 489;
+        return Ok(Exit::Fallback);
+    }
+    // 489: arb #-6
+    m.insp = 489;
+    let a: i64 = -6;
+    m.adjust_relative_base(&a)?;
+    // 491: add #44, #0, rb+6
+    m.insp = 491;
+    let a: i64 = 44;
+    let b: i64 = 0;
+    if is_code(m.save(m.relative_address(&6)?, m.add(&a, &b)?)?) {
+        m.insp = 495;
+        return Ok(Exit::Fallback);
+    }
+    // 495: eq [1014], #47, [63]
+    m.insp = 495;
+    let a: i64 = m.load(1014)?;
+    let b: i64 = 47;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 499;
+        return Ok(Exit::Fallback);
+    }
+    // 499: jnz [63], #505
+    m.insp = 499;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&505)?
+    } else {
+        502
+    };
+    Ok(Exit::Continue)
+}
+
+fn b502(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 502: jz #0, #511
+    m.insp = 502;
+    m.insp = m.jump_target(&511)?;
+    Ok(Exit::Continue)
+}
+
+fn b505(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 505: out [491]
+    m.insp = 505;
+    let a: i64 = m.load(491)?;
+    m.insp = 507;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b507(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 507: add [64], #1, [64]
+    m.insp = 507;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 511;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 511;
+    Ok(Exit::Continue)
+}
+
+fn b511(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 511: mul [64], #2, [64]
+    m.insp = 511;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 515;
+        return Ok(Exit::Fallback);
+    }
+    // 515: arb #-6
+    m.insp = 515;
+    let a: i64 = -6;
+    m.adjust_relative_base(&a)?;
+    // 517: eq rb-1, #32, [63]
+    m.insp = 517;
+    let a: i64 = m.load(m.relative_address(&-1)?)?;
+    let b: i64 = 32;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 521;
+        return Ok(Exit::Fallback);
+    }
+    // 521: jnz [63], #529
+    m.insp = 521;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&529)?
+    } else {
+        524
+    };
+    Ok(Exit::Continue)
+}
+
+fn b524(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 524: out [517]
+    m.insp = 524;
+    let a: i64 = m.load(517)?;
+    m.insp = 526;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b526(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 526: jnz #1, #533
+    m.insp = 526;
+    m.insp = m.jump_target(&533)?;
+    Ok(Exit::Continue)
+}
+
+fn b529(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 529: add [64], #1, [64]
+    m.insp = 529;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 533;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 533;
+    Ok(Exit::Continue)
+}
+
+fn b533(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 533: mul [64], #2, [64]
+    m.insp = 533;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 537;
+        return Ok(Exit::Fallback);
+    }
+    // 537: arb #11
+    m.insp = 537;
+    let a: i64 = 11;
+    m.adjust_relative_base(&a)?;
+    // 539: jnz rb+7, #545
+    m.insp = 539;
+    let a: i64 = m.load(m.relative_address(&7)?)?;
+    m.insp = if a != 0 {
+        m.jump_target(&545)?
+    } else {
+        542
+    };
+    Ok(Exit::Continue)
+}
+
+fn b542(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 542: jz #0, #551
+    m.insp = 542;
+    m.insp = m.jump_target(&551)?;
+    Ok(Exit::Continue)
+}
+
+fn b545(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 545: out [539]
+    m.insp = 545;
+    let a: i64 = m.load(539)?;
+    m.insp = 547;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b547(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 547: add [64], #1, [64]
+    m.insp = 547;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 551;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 551;
+    Ok(Exit::Continue)
+}
+
+fn b551(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 551: mul [64], #2, [64]
+    m.insp = 551;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 555;
+        return Ok(Exit::Fallback);
+    }
+    // 555: arb #11
+    m.insp = 555;
+    let a: i64 = 11;
+    m.adjust_relative_base(&a)?;
+    // 557: mul #45, #1, rb-7
+    m.insp = 557;
+    let a: i64 = 45;
+    let b: i64 = 1;
+    if is_code(m.save(m.relative_address(&-7)?, m.mul(&a, &b)?)?) {
+        m.insp = 561;
+        return Ok(Exit::Fallback);
+    }
+    // 561: eq [1017], #48, [63]
+    m.insp = 561;
+    let a: i64 = m.load(1017)?;
+    let b: i64 = 48;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 565;
+        return Ok(Exit::Fallback);
+    }
+    // 565: jnz [63], #575
+    m.insp = 565;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&575)?
+    } else {
+        568
+    };
+    Ok(Exit::Continue)
+}
+
+fn b568(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 568: add [64], #1, [64]
+    m.insp = 568;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 572;
+        return Ok(Exit::Fallback);
+    }
+    // 572: jz #0, #577
+    m.insp = 572;
+    m.insp = m.jump_target(&577)?;
+    Ok(Exit::Continue)
+}
+
+fn b575(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 575: out [557]
+    m.insp = 575;
+    let a: i64 = m.load(557)?;
+    m.insp = 577;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b577(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 577: mul [64], #2, [64]
+    m.insp = 577;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 581;
+        return Ok(Exit::Fallback);
+    }
+    // 581: arb #-8
+    m.insp = 581;
+    let a: i64 = -8;
+    m.adjust_relative_base(&a)?;
+    // 583: jz rb+5, #593
+    m.insp = 583;
+    let a: i64 = m.load(m.relative_address(&5)?)?;
+    m.insp = if a == 0 {
+        m.jump_target(&593)?
+    } else {
+        586
+    };
+    Ok(Exit::Continue)
+}
+
+fn b586(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 586: add [64], #1, [64]
+    m.insp = 586;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 590;
+        return Ok(Exit::Fallback);
+    }
+    // 590: jnz #1, #595
+    m.insp = 590;
+    m.insp = m.jump_target(&595)?;
+    Ok(Exit::Continue)
+}
+
+fn b593(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 593: out [583]
+    m.insp = 593;
+    let a: i64 = m.load(583)?;
+    m.insp = 595;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b595(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 595: mul [64], #2, [64]
+    m.insp = 595;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 599;
+        return Ok(Exit::Fallback);
+    }
+    // 599: arb #7
+    m.insp = 599;
+    let a: i64 = 7;
+    m.adjust_relative_base(&a)?;
+    // 601: jz rb-3, #609
+    m.insp = 601;
+    let a: i64 = m.load(m.relative_address(&-3)?)?;
+    m.insp = if a == 0 {
+        m.jump_target(&609)?
+    } else {
+        604
+    };
+    Ok(Exit::Continue)
+}
+
+fn b604(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 604: out [601]
+    m.insp = 604;
+    let a: i64 = m.load(601)?;
+    m.insp = 606;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b606(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 606: jz #0, #613
+    m.insp = 606;
+    m.insp = m.jump_target(&613)?;
+    Ok(Exit::Continue)
+}
+
+fn b609(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 609: add [64], #1, [64]
+    m.insp = 609;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 613;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 613;
+    Ok(Exit::Continue)
+}
+
+fn b613(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 613: mul [64], #2, [64]
+    m.insp = 613;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 617;
+        return Ok(Exit::Fallback);
+    }
+    // 617: arb #-10
+    m.insp = 617;
+    let a: i64 = -10;
+    m.adjust_relative_base(&a)?;
+    // 619: add #0, rb-6, [63]
+    m.insp = 619;
+    let a: i64 = 0;
+    let b: i64 = m.load(m.relative_address(&-6)?)?;
+    if is_code(m.save(63, m.add(&a, &b)?)?) {
+        m.insp = 623;
+        return Ok(Exit::Fallback);
+    }
+    // 623: eq [63], #39, [63]
+    m.insp = 623;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 39;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 627;
+        return Ok(Exit::Fallback);
+    }
+    // 627: jnz [63], #635
+    m.insp = 627;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&635)?
+    } else {
+        630
+    };
+    Ok(Exit::Continue)
+}
+
+fn b630(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 630: out [619]
+    m.insp = 630;
+    let a: i64 = m.load(619)?;
+    m.insp = 632;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b632(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 632: jz #0, #639
+    m.insp = 632;
+    m.insp = m.jump_target(&639)?;
+    Ok(Exit::Continue)
+}
+
+fn b635(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 635: add [64], #1, [64]
+    m.insp = 635;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 639;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 639;
+    Ok(Exit::Continue)
+}
+
+fn b639(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 639: mul [64], #2, [64]
+    m.insp = 639;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 643;
+        return Ok(Exit::Fallback);
+    }
+    // 643: arb #-9
+    m.insp = 643;
+    let a: i64 = -9;
+    m.adjust_relative_base(&a)?;
+    // 645: eq rb+0, #39, [63]
+    m.insp = 645;
+    let a: i64 = m.load(m.relative_base)?;
+    let b: i64 = 39;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 649;
+        return Ok(Exit::Fallback);
+    }
+    // 649: jnz [63], #655
+    m.insp = 649;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&655)?
+    } else {
+        652
+    };
+    Ok(Exit::Continue)
+}
+
+fn b652(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 652: jz #0, #661
+    m.insp = 652;
+    m.insp = m.jump_target(&661)?;
+    Ok(Exit::Continue)
+}
+
+fn b655(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 655: out [645]
+    m.insp = 655;
+    let a: i64 = m.load(645)?;
+    m.insp = 657;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b657(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 657: add [64], #1, [64]
+    m.insp = 657;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 661;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 661;
+    Ok(Exit::Continue)
+}
+
+fn b661(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 661: mul [64], #2, [64]
+    m.insp = 661;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 665;
+        return Ok(Exit::Fallback);
+    }
+    // 665: arb #4
+    m.insp = 665;
+    let a: i64 = 4;
+    m.adjust_relative_base(&a)?;
+    // 667: lt #25, rb+0, [63]
+    m.insp = 667;
+    let a: i64 = 25;
+    let b: i64 = m.load(m.relative_base)?;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 671;
+        return Ok(Exit::Fallback);
+    }
+    // 671: jnz [63], #681
+    m.insp = 671;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&681)?
+    } else {
+        674
+    };
+    Ok(Exit::Continue)
+}
+
+fn b674(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 674: add [64], #1, [64]
+    m.insp = 674;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 678;
+        return Ok(Exit::Fallback);
+    }
+    // 678: jnz #1, #683
+    m.insp = 678;
+    m.insp = m.jump_target(&683)?;
+    Ok(Exit::Continue)
+}
+
+fn b681(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 681: out [667]
+    m.insp = 681;
+    let a: i64 = m.load(667)?;
+    m.insp = 683;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b683(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 683: mul [64], #2, [64]
+    m.insp = 683;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 687;
+        return Ok(Exit::Fallback);
+    }
+    // 687: arb #-5
+    m.insp = 687;
+    let a: i64 = -5;
+    m.adjust_relative_base(&a)?;
+    // 689: lt #31, rb-2, [63]
+    m.insp = 689;
+    let a: i64 = 31;
+    let b: i64 = m.load(m.relative_address(&-2)?)?;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 693;
+        return Ok(Exit::Fallback);
+    }
+    // 693: jnz [63], #701
+    m.insp = 693;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&701)?
+    } else {
+        696
+    };
+    Ok(Exit::Continue)
+}
+
+fn b696(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 696: out [689]
+    m.insp = 696;
+    let a: i64 = m.load(689)?;
+    m.insp = 698;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b698(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 698: jz #0, #705
+    m.insp = 698;
+    m.insp = m.jump_target(&705)?;
+    Ok(Exit::Continue)
+}
+
+fn b701(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 701: add [64], #1, [64]
+    m.insp = 701;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 705;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 705;
+    Ok(Exit::Continue)
+}
+
+fn b705(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 705: mul [64], #2, [64]
+    m.insp = 705;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 709;
+        return Ok(Exit::Fallback);
+    }
+    // 709: arb #19
+    m.insp = 709;
+    let a: i64 = 19;
+    m.adjust_relative_base(&a)?;
+    // 711: jnz rb-1, #719
+    m.insp = 711;
+    let a: i64 = m.load(m.relative_address(&-1)?)?;
+    m.insp = if a != 0 {
+        m.jump_target(&719)?
+    } else {
+        714
+    };
+    Ok(Exit::Continue)
+}
+
+fn b714(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 714: out [711]
+    m.insp = 714;
+    let a: i64 = m.load(711)?;
+    m.insp = 716;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b716(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 716: jnz #1, #723
+    m.insp = 716;
+    m.insp = m.jump_target(&723)?;
+    Ok(Exit::Continue)
+}
+
+fn b719(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 719: add [64], #1, [64]
+    m.insp = 719;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 723;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 723;
+    Ok(Exit::Continue)
+}
+
+fn b723(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 723: mul [64], #2, [64]
+    m.insp = 723;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 727;
+        return Ok(Exit::Fallback);
+    }
+    // 727: arb #-17
+    m.insp = 727;
+    let a: i64 = -17;
+    m.adjust_relative_base(&a)?;
+    // 729: add rb+3, #0, [63]
+    m.insp = 729;
+    let a: i64 = m.load(m.relative_address(&3)?)?;
+    let b: i64 = 0;
+    if is_code(m.save(63, m.add(&a, &b)?)?) {
+        m.insp = 733;
+        return Ok(Exit::Fallback);
+    }
+    // 733: eq [63], #24, [63]
+    m.insp = 733;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 24;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 737;
+        return Ok(Exit::Fallback);
+    }
+    // 737: jnz [63], #745
+    m.insp = 737;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&745)?
+    } else {
+        740
+    };
+    Ok(Exit::Continue)
+}
+
+fn b740(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 740: out [729]
+    m.insp = 740;
+    let a: i64 = m.load(729)?;
+    m.insp = 742;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b742(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 742: jz #0, #749
+    m.insp = 742;
+    m.insp = m.jump_target(&749)?;
+    Ok(Exit::Continue)
+}
+
+fn b745(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 745: add [64], #1, [64]
+    m.insp = 745;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 749;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 749;
+    Ok(Exit::Continue)
+}
+
+fn b749(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 749: mul [64], #2, [64]
+    m.insp = 749;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 753;
+        return Ok(Exit::Fallback);
+    }
+    // 753: arb #13
+    m.insp = 753;
+    let a: i64 = 13;
+    m.adjust_relative_base(&a)?;
+    // 755: mul #46, #1, rb-3
+    m.insp = 755;
+    let a: i64 = 46;
+    let b: i64 = 1;
+    if is_code(m.save(m.relative_address(&-3)?, m.mul(&a, &b)?)?) {
+        m.insp = 759;
+        return Ok(Exit::Fallback);
+    }
+    // 759: eq [1015], #46, [63]
+    m.insp = 759;
+    let a: i64 = m.load(1015)?;
+    let b: i64 = 46;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 763;
+        return Ok(Exit::Fallback);
+    }
+    // 763: jnz [63], #771
+    m.insp = 763;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&771)?
+    } else {
+        766
+    };
+    Ok(Exit::Continue)
+}
+
+fn b766(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 766: out [755]
+    m.insp = 766;
+    let a: i64 = m.load(755)?;
+    m.insp = 768;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b768(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 768: jnz #1, #775
+    m.insp = 768;
+    m.insp = m.jump_target(&775)?;
+    Ok(Exit::Continue)
+}
+
+fn b771(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 771: add [64], #1, [64]
+    m.insp = 771;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 775;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 775;
+    Ok(Exit::Continue)
+}
+
+fn b775(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 775: mul [64], #2, [64]
+    m.insp = 775;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 779;
+        return Ok(Exit::Fallback);
+    }
+    // 779: arb #-13
+    m.insp = 779;
+    let a: i64 = -13;
+    m.adjust_relative_base(&a)?;
+    // 781: lt rb+4, #32, [63]
+    m.insp = 781;
+    let a: i64 = m.load(m.relative_address(&4)?)?;
+    let b: i64 = 32;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 785;
+        return Ok(Exit::Fallback);
+    }
+    // 785: jnz [63], #793
+    m.insp = 785;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&793)?
+    } else {
+        788
+    };
+    Ok(Exit::Continue)
+}
+
+fn b788(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 788: out [781]
+    m.insp = 788;
+    let a: i64 = m.load(781)?;
+    m.insp = 790;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b790(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 790: jz #0, #797
+    m.insp = 790;
+    m.insp = m.jump_target(&797)?;
+    Ok(Exit::Continue)
+}
+
+fn b793(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 793: add [64], #1, [64]
+    m.insp = 793;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 797;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 797;
+    Ok(Exit::Continue)
+}
+
+fn b797(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 797: mul [64], #2, [64]
+    m.insp = 797;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 801;
+        return Ok(Exit::Fallback);
+    }
+    // 801: arb #7
+    m.insp = 801;
+    let a: i64 = 7;
+    m.adjust_relative_base(&a)?;
+    // 803: mul #1, rb-9, [63]
+    m.insp = 803;
+    let a: i64 = 1;
+    let b: i64 = m.load(m.relative_address(&-9)?)?;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 807;
+        return Ok(Exit::Fallback);
+    }
+    // 807: eq [63], #27, [63]
+    m.insp = 807;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 27;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 811;
+        return Ok(Exit::Fallback);
+    }
+    // 811: jnz [63], #821
+    m.insp = 811;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&821)?
+    } else {
+        814
+    };
+    Ok(Exit::Continue)
+}
+
+fn b814(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 814: add [64], #1, [64]
+    m.insp = 814;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 818;
+        return Ok(Exit::Fallback);
+    }
+    // 818: jnz #1, #823
+    m.insp = 818;
+    m.insp = m.jump_target(&823)?;
+    Ok(Exit::Continue)
+}
+
+fn b821(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 821: out [803]
+    m.insp = 821;
+    let a: i64 = m.load(803)?;
+    m.insp = 823;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b823(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 823: mul [64], #2, [64]
+    m.insp = 823;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 827;
+        return Ok(Exit::Fallback);
+    }
+    // 827: arb #-18
+    m.insp = 827;
+    let a: i64 = -18;
+    m.adjust_relative_base(&a)?;
+    // 829: add rb+8, #0, [63]
+    m.insp = 829;
+    let a: i64 = m.load(m.relative_address(&8)?)?;
+    let b: i64 = 0;
+    if is_code(m.save(63, m.add(&a, &b)?)?) {
+        m.insp = 833;
+        return Ok(Exit::Fallback);
+    }
+    // 833: eq [63], #25, [63]
+    m.insp = 833;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 25;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 837;
+        return Ok(Exit::Fallback);
+    }
+    // 837: jnz [63], #847
+    m.insp = 837;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&847)?
+    } else {
+        840
+    };
+    Ok(Exit::Continue)
+}
+
+fn b840(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 840: add [64], #1, [64]
+    m.insp = 840;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 844;
+        return Ok(Exit::Fallback);
+    }
+    // 844: jz #0, #849
+    m.insp = 844;
+    m.insp = m.jump_target(&849)?;
+    Ok(Exit::Continue)
+}
+
+fn b847(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 847: out [829]
+    m.insp = 847;
+    let a: i64 = m.load(829)?;
+    m.insp = 849;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b849(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 849: mul [64], #2, [64]
+    m.insp = 849;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 853;
+        return Ok(Exit::Fallback);
+    }
+    // 853: arb #23
+    m.insp = 853;
+    let a: i64 = 23;
+    m.adjust_relative_base(&a)?;
+    // 855: add #47, #0, rb+2
+    m.insp = 855;
+    let a: i64 = 47;
+    let b: i64 = 0;
+    if is_code(m.save(m.relative_address(&2)?, m.add(&a, &b)?)?) {
+        m.insp = 859;
+        return Ok(Exit::Fallback);
+    }
+    // 859: eq [1019], #47, [63]
+    m.insp = 859;
+    let a: i64 = m.load(1019)?;
+    let b: i64 = 47;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 863;
+        return Ok(Exit::Fallback);
+    }
+    // 863: jnz [63], #871
+    m.insp = 863;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&871)?
+    } else {
+        866
+    };
+    Ok(Exit::Continue)
+}
+
+fn b866(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 866: out [855]
+    m.insp = 866;
+    let a: i64 = m.load(855)?;
+    m.insp = 868;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b868(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 868: jz #0, #875
+    m.insp = 868;
+    m.insp = m.jump_target(&875)?;
+    Ok(Exit::Continue)
+}
+
+fn b871(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 871: add [64], #1, [64]
+    m.insp = 871;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 875;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 875;
+    Ok(Exit::Continue)
+}
+
+fn b875(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 875: mul [64], #2, [64]
+    m.insp = 875;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 2;
+    if is_code(m.save(64, m.mul(&a, &b)?)?) {
+        m.insp = 879;
+        return Ok(Exit::Fallback);
+    }
+    // 879: arb #-22
+    m.insp = 879;
+    let a: i64 = -22;
+    m.adjust_relative_base(&a)?;
+    // 881: mul rb+5, #1, [63]
+    m.insp = 881;
+    let a: i64 = m.load(m.relative_address(&5)?)?;
+    let b: i64 = 1;
+    if is_code(m.save(63, m.mul(&a, &b)?)?) {
+        m.insp = 885;
+        return Ok(Exit::Fallback);
+    }
+    // 885: eq [63], #19, [63]
+    m.insp = 885;
+    let a: i64 = m.load(63)?;
+    let b: i64 = 19;
+    if is_code(m.save(63, i64::from(a == b))?) {
+        m.insp = 889;
+        return Ok(Exit::Fallback);
+    }
+    // 889: jnz [63], #899
+    m.insp = 889;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&899)?
+    } else {
+        892
+    };
+    Ok(Exit::Continue)
+}
+
+fn b892(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 892: add [64], #1, [64]
+    m.insp = 892;
+    let a: i64 = m.load(64)?;
+    let b: i64 = 1;
+    if is_code(m.save(64, m.add(&a, &b)?)?) {
+        m.insp = 896;
+        return Ok(Exit::Fallback);
+    }
+    // 896: jz #0, #901
+    m.insp = 896;
+    m.insp = m.jump_target(&901)?;
+    Ok(Exit::Continue)
+}
+
+fn b899(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 899: out [881]
+    m.insp = 899;
+    let a: i64 = m.load(881)?;
+    m.insp = 901;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b901(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 901: out [64]
+    m.insp = 901;
+    let a: i64 = m.load(64)?;
+    m.insp = 903;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b903(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 903: hlt
+    m.insp = 903;
+    m.halt();
+    Ok(Exit::Halt)
+}
+
+fn b904(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 904: mul #27, #1, rb+1
+    m.insp = 904;
+    let a: i64 = 27;
+    let b: i64 = 1;
+    if is_code(m.save(m.relative_address(&1)?, m.mul(&a, &b)?)?) {
+        m.insp = 908;
+        return Ok(Exit::Fallback);
+    }
+    // 908: mul #1, #915, rb+0
+    m.insp = 908;
+    let a: i64 = 1;
+    let b: i64 = 915;
+    if is_code(m.save(m.relative_base, m.mul(&a, &b)?)?) {
+        m.insp = 912;
+        return Ok(Exit::Fallback);
+    }
+    // 912: jnz #1, #922
+    m.insp = 912;
+    m.insp = m.jump_target(&922)?;
+    Ok(Exit::Continue)
+}
+
+fn b915(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 915: add rb+1, #25165, rb+1
+    m.insp = 915;
+    let a: i64 = m.load(m.relative_address(&1)?)?;
+    let b: i64 = 25165;
+    if is_code(m.save(m.relative_address(&1)?, m.add(&a, &b)?)?) {
+        m.insp = 919;
+        return Ok(Exit::Fallback);
+    }
+    // 919: out rb+1
+    m.insp = 919;
+    let a: i64 = m.load(m.relative_address(&1)?)?;
+    m.insp = 921;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b921(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 921: hlt
+    m.insp = 921;
+    m.halt();
+    Ok(Exit::Halt)
+}
+
+fn b922(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 922: arb #3
+    m.insp = 922;
+    let a: i64 = 3;
+    m.adjust_relative_base(&a)?;
+    // 924: lt rb-2, #3, [63]
+    m.insp = 924;
+    let a: i64 = m.load(m.relative_address(&-2)?)?;
+    let b: i64 = 3;
+    if is_code(m.save(63, i64::from(a < b))?) {
+        m.insp = 928;
+        return Ok(Exit::Fallback);
+    }
+    // 928: jnz [63], #964
+    m.insp = 928;
+    let a: i64 = m.load(63)?;
+    m.insp = if a != 0 {
+        m.jump_target(&964)?
+    } else {
+        931
+    };
+    Ok(Exit::Continue)
+}
+
+fn b931(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 931: add rb-2, #-1, rb+1
+    m.insp = 931;
+    let a: i64 = m.load(m.relative_address(&-2)?)?;
+    let b: i64 = -1;
+    if is_code(m.save(m.relative_address(&1)?, m.add(&a, &b)?)?) {
+        m.insp = 935;
+        return Ok(Exit::Fallback);
+    }
+    // 935: mul #942, #1, rb+0
+    m.insp = 935;
+    let a: i64 = 942;
+    let b: i64 = 1;
+    if is_code(m.save(m.relative_base, m.mul(&a, &b)?)?) {
+        m.insp = 939;
+        return Ok(Exit::Fallback);
+    }
+    // 939: jnz #1, #922
+    m.insp = 939;
+    m.insp = m.jump_target(&922)?;
+    Ok(Exit::Continue)
+}
+
+fn b942(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 942: mul #1, rb+1, rb-1
+    m.insp = 942;
+    let a: i64 = 1;
+    let b: i64 = m.load(m.relative_address(&1)?)?;
+    if is_code(m.save(m.relative_address(&-1)?, m.mul(&a, &b)?)?) {
+        m.insp = 946;
+        return Ok(Exit::Fallback);
+    }
+    // 946: add rb-2, #-3, rb+1
+    m.insp = 946;
+    let a: i64 = m.load(m.relative_address(&-2)?)?;
+    let b: i64 = -3;
+    if is_code(m.save(m.relative_address(&1)?, m.add(&a, &b)?)?) {
+        m.insp = 950;
+        return Ok(Exit::Fallback);
+    }
+    // 950: add #0, #957, rb+0
+    m.insp = 950;
+    let a: i64 = 0;
+    let b: i64 = 957;
+    if is_code(m.save(m.relative_base, m.add(&a, &b)?)?) {
+        m.insp = 954;
+        return Ok(Exit::Fallback);
+    }
+    // 954: jnz #1, #922
+    m.insp = 954;
+    m.insp = m.jump_target(&922)?;
+    Ok(Exit::Continue)
+}
+
+fn b957(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 957: add rb+1, rb-1, rb-2
+    m.insp = 957;
+    let a: i64 = m.load(m.relative_address(&1)?)?;
+    let b: i64 = m.load(m.relative_address(&-1)?)?;
+    if is_code(m.save(m.relative_address(&-2)?, m.add(&a, &b)?)?) {
+        m.insp = 961;
+        return Ok(Exit::Fallback);
+    }
+    // 961: jz #0, #968
+    m.insp = 961;
+    m.insp = m.jump_target(&968)?;
+    Ok(Exit::Continue)
+}
+
+fn b964(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 964: add rb-2, #0, rb-2
+    m.insp = 964;
+    let a: i64 = m.load(m.relative_address(&-2)?)?;
+    let b: i64 = 0;
+    if is_code(m.save(m.relative_address(&-2)?, m.add(&a, &b)?)?) {
+        m.insp = 968;
+        return Ok(Exit::Fallback);
+    }
+    m.insp = 968;
+    Ok(Exit::Continue)
+}
+
+fn b968(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 968: arb #-3
+    m.insp = 968;
+    let a: i64 = -3;
+    m.adjust_relative_base(&a)?;
+    // 970: jnz #1, rb+0
+    m.insp = 970;
+    m.insp = m.jump_target(&m.load(m.relative_base)?)?;
+    Ok(Exit::Continue)
+}
@@ -0,0 +1,68 @@
+// Compiled from the ECHO test program by the transpile tool, do not edit.
+use crate::intcode::native::{Compiled, Exit};
+use crate::intcode::{IntcodeError, Interpreter};
+
+pub const COMPILED: Compiled = Compiled {
+    len: 8,
+    code: &[(0, 8)],
+    checksum: 0xaa0ffb2183741bd1,
+    dispatch,
+};
+
+pub fn dispatch(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    match m.insp {
+        0 => b0(m),
+        4 => b4(m),
+        7 => b7(m),
+        _ => Ok(Exit::Fallback),
+    }
+}
+
+fn is_code(address: usize) -> bool {
+    matches!(
+        address,
+        0..=7
+    )
+}
+
+fn b0(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 0: in [3]
+    m.insp = 0;
+    let value = match m.peek_input() {
+        Some(value) => value,
+        None => return Ok(Exit::NeedsInput),
+    };
+    let address = m.save(3, value)?;
+    m.consume_input();
+    if is_code(address) {
+        m.insp = 2;
+        return Ok(Exit::Fallback);
+    }
+    // 2: out #0
+    m.insp = 2;
+    let a: i64 = 0;
+    m.insp = 4;
+    if let Some(a) = m.send(a) {
+        return Ok(Exit::Output(a));
+    }
+    Ok(Exit::Continue)
+}
+
+fn b4(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 4: jnz [3], #0
+    m.insp = 4;
+    let a: i64 = m.load(3)?;
+    m.insp = if a != 0 {
+        m.jump_target(&0)?
+    } else {
+        7
+    };
+    Ok(Exit::Continue)
+}
+
+fn b7(m: &mut Interpreter) -> Result<Exit, IntcodeError> {
+    // 7: hlt
+    m.insp = 7;
+    m.halt();
+    Ok(Exit::Halt)
+}
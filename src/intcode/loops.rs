@@ -0,0 +1,212 @@
+use super::{IntcodeError, Interpreter, Memory, Word};
+use std::collections::VecDeque;
+
+// Everything that decides how the program continues without I/O.
+#[derive(Debug, Clone, PartialEq)]
+struct State<W: Word> {
+    insp: usize,
+    relative_base: W,
+    memory: Memory<W>,
+    input: VecDeque<W>,
+}
+
+// Brent's cycle detection over the states at backward jumps since the last
+// input or output. Without I/O the program is deterministic, so reaching the
+// saved state again means it loops forever. Only one state is kept: it is
+// replaced at the 1st, 2nd, 4th, 8th, ... jump after the last one, so a loop
+// is found within about twice the number of jumps it takes to enter and go
+// around it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopDetector<W: Word = i64> {
+    saved: Option<State<W>>,
+    // Backward jumps since the state was saved, and the count at which the
+    // next one is saved.
+    jumps: u64,
+    limit: u64,
+    // Address range executed since the state was saved.
+    range: Option<(usize, usize)>,
+}
+
+impl<W: Word> Default for LoopDetector<W> {
+    fn default() -> Self {
+        LoopDetector {
+            saved: None,
+            jumps: 0,
+            limit: 1,
+            range: None,
+        }
+    }
+}
+
+impl<W: Word> LoopDetector<W> {
+    fn executed(&mut self, start: usize, end: usize) {
+        self.range = Some(match self.range {
+            Some((s, e)) => (s.min(start), e.max(end)),
+            None => (start, end),
+        });
+    }
+
+    fn reset(&mut self) {
+        *self = LoopDetector::default();
+    }
+
+    // Whether a backward jump to insp could return to the saved state, so
+    // the full state is only built when needed.
+    fn may_repeat(&self, insp: usize, relative_base: &W) -> bool {
+        self.jumps + 1 >= self.limit
+            || self
+                .saved
+                .as_ref()
+                .is_some_and(|saved| saved.insp == insp && saved.relative_base == *relative_base)
+    }
+
+    // Check the state after a backward jump. Returns the address range of the
+    // loop if it is the saved state.
+    fn backward_jump(&mut self, state: State<W>) -> Option<(usize, usize)> {
+        if self.saved.as_ref() == Some(&state) {
+            return self.range;
+        }
+        self.jumps += 1;
+        if self.jumps >= self.limit {
+            self.saved = Some(state);
+            self.jumps = 0;
+            self.limit *= 2;
+            self.range = None;
+        }
+        None
+    }
+
+    // Count a backward jump that can't return to the saved state.
+    fn skip(&mut self) {
+        self.jumps += 1;
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    // Fail with IntcodeError::InfiniteLoop when the program returns to the
+    // same state at a backward jump without input or output in between.
+    // Memory is copied at exponentially spaced backward jumps and compared at
+    // the others, so this slows down long stretches without I/O.
+    pub fn detect_loops(&mut self, enabled: bool) {
+        self.loop_detector = if enabled {
+            Some(LoopDetector::default())
+        } else {
+            None
+        };
+    }
+
+    fn state(&self) -> State<W> {
+        State {
+            insp: self.insp,
+            relative_base: self.relative_base.clone(),
+            memory: self.memory.clone(),
+            input: self.input.clone(),
+        }
+    }
+
+    // Update loop detection after the instruction at insp was executed.
+    pub(super) fn check_loop(&mut self, insp: usize, opcode: i64) -> Result<(), IntcodeError> {
+        // Handlers of custom instructions may do I/O of their own.
+        let io = matches!(opcode % 100, 3 | 4) || self.custom_opcodes.contains_key(&(opcode % 100));
+        let detector = match self.loop_detector.as_mut() {
+            Some(detector) => detector,
+            None => return Ok(()),
+        };
+        if io {
+            detector.reset();
+            return Ok(());
+        }
+        let len = match opcode % 100 {
+            1 | 2 | 7 | 8 => 4,
+            5 | 6 => 3,
+            _ => 2,
+        };
+        detector.executed(insp, insp + len);
+        if !matches!(opcode % 100, 5 | 6) || self.insp > insp {
+            return Ok(());
+        }
+        if !detector.may_repeat(self.insp, &self.relative_base) {
+            detector.skip();
+            return Ok(());
+        }
+        let state = self.state();
+        let range = self
+            .loop_detector
+            .as_mut()
+            .and_then(|detector| detector.backward_jump(state));
+        match range {
+            Some((start, end)) => Err(IntcodeError::InfiniteLoop {
+                insp,
+                opcode,
+                start,
+                end,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::RunOutput;
+
+    #[test]
+    fn test_infinite_loop() {
+        // Adds 1 to cell 14 until it is 3, then loops at 8..14 forever.
+        let program = "1001,14,1,14,1008,14,3,15,1006,15,0,1105,1,8,0,0";
+        let mut t = Interpreter::new(program, &[]);
+        t.detect_loops(true);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::InfiniteLoop {
+                insp: 11,
+                opcode: 1105,
+                start: 8,
+                end: 14,
+            })
+        );
+    }
+
+    #[test]
+    fn test_long_loop() {
+        // Counts cell 11 down from 10000 with 100000 cells of memory, then
+        // runs the instruction at 7. Keeping the memory of every backward
+        // jump would take 8 GB.
+        let countdown = |end: &str| {
+            let program = format!("1001,11,-1,11,1005,11,0,{},0,10000", end);
+            Interpreter::new(&(program + &",0".repeat(100_000)), &[])
+        };
+
+        let mut t = countdown("99,0,0");
+        t.detect_loops(true);
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+
+        let mut t = countdown("1105,1,7");
+        t.detect_loops(true);
+        assert_eq!(
+            t.run(),
+            Err(IntcodeError::InfiniteLoop {
+                insp: 7,
+                opcode: 1105,
+                start: 7,
+                end: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn test_io_resets() {
+        // Outputs 7 in a loop: the state repeats, but with output in between.
+        let mut t = Interpreter::new("104,7,1105,1,0", &[]);
+        t.detect_loops(true);
+        for _ in 0..3 {
+            assert_eq!(t.run(), Ok(RunOutput::Output(7)));
+        }
+
+        // Counts down from 1000 without repeating a state.
+        let mut t = Interpreter::new("1001,9,-1,9,1005,9,0,104,1,1000", &[]);
+        t.detect_loops(true);
+        assert_eq!(t.run(), Ok(RunOutput::Output(1)));
+    }
+}
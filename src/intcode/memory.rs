@@ -0,0 +1,131 @@
+use super::Word;
+use std::collections::HashMap;
+
+// Writes this far past the end of the dense region go to sparse storage
+// instead of growing it.
+const MAX_DENSE_GAP: usize = 4096;
+
+// Intcode memory. Cells near the program are kept in a vector that grows on
+// demand, far away addresses are stored sparsely. Untouched cells read as zero.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Memory<W = i64> {
+    dense: Vec<W>,
+    sparse: HashMap<usize, W>,
+}
+
+impl<W: Word> Memory<W> {
+    pub fn new(cells: Vec<W>) -> Self {
+        Memory {
+            dense: cells,
+            sparse: HashMap::new(),
+        }
+    }
+
+    // Restore memory from the dense cells and the sparse cells past them.
+    pub fn from_parts(dense: Vec<W>, sparse: HashMap<usize, W>) -> Self {
+        Memory { dense, sparse }
+    }
+
+    #[inline(always)]
+    pub fn read(&self, address: usize) -> W {
+        match self.dense.get(address) {
+            Some(value) => value.clone(),
+            None => self.read_sparse(address),
+        }
+    }
+
+    #[inline(always)]
+    pub fn write(&mut self, address: usize, value: W) {
+        match self.dense.get_mut(address) {
+            Some(cell) => *cell = value,
+            None => self.write_sparse(address, value),
+        }
+    }
+
+    // Accesses past the dense region are rare, keeping them out of line
+    // leaves the interpreter loop small.
+    #[cold]
+    #[inline(never)]
+    fn read_sparse(&self, address: usize) -> W {
+        self.sparse.get(&address).cloned().unwrap_or_default()
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_sparse(&mut self, address: usize, value: W) {
+        if address - self.dense.len() < self.dense.len().max(MAX_DENSE_GAP) {
+            self.grow(address + 1);
+            self.dense[address] = value;
+        } else if !value.is_zero() {
+            self.sparse.insert(address, value);
+        } else {
+            self.sparse.remove(&address);
+        }
+    }
+
+    // Extend the dense region, moving any sparse cells it now covers.
+    fn grow(&mut self, len: usize) {
+        let old_len = self.dense.len();
+        self.dense.resize(len, W::default());
+        if self.sparse.is_empty() {
+            return;
+        }
+        for address in old_len..len {
+            if let Some(value) = self.sparse.remove(&address) {
+                self.dense[address] = value;
+            }
+        }
+    }
+
+    // Number of cells backed by the dense region.
+    pub fn dense_len(&self) -> usize {
+        self.dense.len()
+    }
+
+    pub fn dense_cells(&self) -> &[W] {
+        &self.dense
+    }
+
+    // Non-zero cells outside of the dense region, sorted by address.
+    pub fn sparse_cells(&self) -> Vec<(usize, W)> {
+        let mut cells: Vec<(usize, W)> = self.sparse.iter().map(|(&a, v)| (a, v.clone())).collect();
+        cells.sort_unstable_by_key(|&(address, _)| address);
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grow_on_write() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+        assert_eq!(memory.read(100), 0);
+        memory.write(100, 7);
+        assert_eq!(memory.read(100), 7);
+        assert_eq!(memory.dense_len(), 101);
+        assert_eq!(memory.read(2), 3);
+    }
+
+    #[test]
+    fn test_sparse_high_address() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+        memory.write(1 << 40, 5);
+        assert_eq!(memory.read(1 << 40), 5);
+        assert_eq!(memory.read((1 << 40) + 1), 0);
+        assert_eq!(memory.dense_len(), 3);
+    }
+
+    #[test]
+    fn test_grow_over_sparse_cells() {
+        let mut memory = Memory::new(vec![0; 10]);
+        memory.write(10_000, 5);
+        assert_eq!(memory.dense_len(), 10);
+        memory.write(4_000, 1);
+        memory.write(8_000, 1);
+        memory.write(10_010, 1);
+        assert_eq!(memory.dense_len(), 10_011);
+        assert_eq!(memory.read(10_000), 5);
+    }
+}
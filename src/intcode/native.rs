@@ -0,0 +1,133 @@
+use super::{IntcodeError, Interpreter, RunOutput};
+
+// How a compiled block left the program.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Exit {
+    // insp points to the next block.
+    Continue,
+    Output(i64),
+    NeedsInput,
+    Halt,
+    // Code was modified or insp is not at a compiled block, the rest has to
+    // be interpreted.
+    Fallback,
+}
+
+// Entry point of a module generated by transpile, runs the block at insp.
+pub type Dispatch = fn(&mut Interpreter) -> Result<Exit, IntcodeError>;
+
+// FNV-1a hash of the cells in the address ranges. Unlike DefaultHasher the
+// result is fixed, so it can be written into generated code.
+pub(super) fn checksum(ranges: &[(usize, usize)], read: impl Fn(usize) -> i64) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &(start, end) in ranges {
+        for address in start..end {
+            for byte in read(address).to_le_bytes().iter() {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+    hash
+}
+
+// Program compiled by transpile, exported as COMPILED by the generated module.
+#[derive(Debug, Copy, Clone)]
+pub struct Compiled {
+    // Number of cells of the program.
+    pub len: usize,
+    // Address ranges of the compiled instructions.
+    pub code: &'static [(usize, usize)],
+    // Checksum of the cells in code.
+    pub checksum: u64,
+    pub dispatch: Dispatch,
+}
+
+impl Compiled {
+    // Whether the interpreter holds the program the module was compiled from,
+    // as far as the compiled code is concerned.
+    pub fn matches(&self, interpreter: &Interpreter) -> bool {
+        interpreter.memory().dense_len() == self.len
+            && checksum(self.code, |address| interpreter.read_memory(address)) == self.checksum
+    }
+}
+
+// Program compiled to Rust, running on the interpreter state. Once the compiled
+// code can't continue, the interpreter takes over for the rest of the run.
+// Tracing, recording and other instrumentation only apply to the
+// interpreted part.
+pub struct Native {
+    interpreter: Interpreter,
+    dispatch: Dispatch,
+    fallback: bool,
+}
+
+impl Native {
+    // Runs on the interpreter from the start if its memory doesn't match the
+    // compiled program, like another program or one patched with
+    // write_memory.
+    pub fn new(interpreter: Interpreter, compiled: Compiled) -> Self {
+        Native {
+            fallback: !compiled.matches(&interpreter),
+            interpreter,
+            dispatch: compiled.dispatch,
+        }
+    }
+
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    pub fn put_input(&mut self, input: i64) {
+        self.interpreter.put_input(input);
+    }
+
+    // Whether execution has moved over to the interpreter.
+    pub fn is_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    pub fn run(&mut self) -> Result<RunOutput, IntcodeError> {
+        while !self.fallback {
+            match (self.dispatch)(&mut self.interpreter)? {
+                Exit::Continue => {}
+                Exit::Output(value) => return Ok(RunOutput::Output(value)),
+                Exit::NeedsInput => return Ok(RunOutput::NeedsInput),
+                Exit::Halt => return Ok(RunOutput::Halt),
+                Exit::Fallback => self.fallback = true,
+            }
+        }
+        self.interpreter.run()
+    }
+
+    pub fn run_to_halt(&mut self) -> Result<Vec<i64>, IntcodeError> {
+        let mut output = vec![];
+        loop {
+            match self.run()? {
+                RunOutput::Output(value) => output.push(value),
+                RunOutput::Halt => return Ok(output),
+                RunOutput::Watch(hit) => output.extend(hit.output),
+                RunOutput::NeedsInput => {
+                    return Err(IntcodeError::NoInput {
+                        insp: self.interpreter.insp,
+                        opcode: self.interpreter.read_memory(self.interpreter.insp),
+                    })
+                }
+            }
+        }
+    }
+}
+
+// Operations used by generated code, with the same checks as the interpreter.
+impl Interpreter {
+    pub(super) fn load(&self, address: i64) -> Result<i64, IntcodeError> {
+        Ok(self.read_memory(self.check_address(&address)?))
+    }
+
+    // Returns the written address.
+    pub(super) fn save(&mut self, address: i64, value: i64) -> Result<usize, IntcodeError> {
+        let address = self.check_address(&address)?;
+        self.store(address, value);
+        Ok(address)
+    }
+}
@@ -0,0 +1,197 @@
+use super::{Interpreter, Opcode, Word};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct AddressCount {
+    // Raw opcode last executed at the address.
+    pub opcode: i64,
+    pub count: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct BranchCount {
+    pub taken: u64,
+    pub not_taken: u64,
+}
+
+// Execution counts of a program run.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    pub instructions: u64,
+    pub addresses: HashMap<usize, AddressCount>,
+    // Counts by opcode number, without parameter modes.
+    pub opcodes: BTreeMap<i64, u64>,
+    // Jump instructions by address.
+    pub branches: BTreeMap<usize, BranchCount>,
+}
+
+fn mnemonic(opcode: i64) -> &'static str {
+    Opcode::new(opcode)
+        .and_then(|o| o.mnemonic())
+        .unwrap_or("?")
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+impl Profile {
+    // Count an executed instruction. Jumps pass whether they were taken.
+    pub fn record(&mut self, insp: usize, opcode: i64, taken: Option<bool>) {
+        self.instructions += 1;
+        let address = self.addresses.entry(insp).or_default();
+        address.opcode = opcode;
+        address.count += 1;
+        *self.opcodes.entry(opcode % 100).or_insert(0) += 1;
+        if let Some(taken) = taken {
+            let branch = self.branches.entry(insp).or_default();
+            if taken {
+                branch.taken += 1;
+            } else {
+                branch.not_taken += 1;
+            }
+        }
+    }
+
+    // Addresses sorted by execution count, most executed first.
+    pub fn hot_spots(&self) -> Vec<(usize, AddressCount)> {
+        let mut spots: Vec<_> = self.addresses.iter().map(|(&a, &c)| (a, c)).collect();
+        spots.sort_by_key(|&(address, c)| (std::cmp::Reverse(c.count), address));
+        spots
+    }
+
+    // Text report with the `limit` hottest addresses, opcode counts and
+    // branch ratios.
+    pub fn report(&self, limit: usize) -> String {
+        let total = self.instructions;
+        let mut s = String::new();
+        writeln!(s, "instructions: {}", total).unwrap();
+
+        writeln!(s, "\nhot spots:\n  address       count       %  opcode").unwrap();
+        for (address, c) in self.hot_spots().into_iter().take(limit) {
+            writeln!(
+                s,
+                "  {:>7} {:>11} {:>6.2}%  {}",
+                address,
+                c.count,
+                percent(c.count, total),
+                mnemonic(c.opcode)
+            )
+            .unwrap();
+        }
+
+        writeln!(s, "\nopcodes:\n  opcode        count       %").unwrap();
+        let mut opcodes: Vec<_> = self.opcodes.iter().collect();
+        opcodes.sort_by_key(|&(&opcode, &count)| (std::cmp::Reverse(count), opcode));
+        for (&opcode, &count) in opcodes {
+            writeln!(
+                s,
+                "  {:<6} {:>12} {:>6.2}%",
+                mnemonic(opcode),
+                count,
+                percent(count, total)
+            )
+            .unwrap();
+        }
+
+        writeln!(s, "\nbranches:\n  address       taken   not taken   taken%").unwrap();
+        for (address, b) in self.branches.iter() {
+            writeln!(
+                s,
+                "  {:>7} {:>11} {:>11} {:>7.2}%",
+                address,
+                b.taken,
+                b.not_taken,
+                percent(b.taken, b.taken + b.not_taken)
+            )
+            .unwrap();
+        }
+        s
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    // Count executed instructions from now on.
+    pub fn start_profile(&mut self) {
+        self.profile = Some(Profile::default());
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    // Stop profiling and return the counts.
+    pub fn take_profile(&mut self) -> Option<Profile> {
+        self.profile.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::fixtures::COUNTDOWN;
+
+    #[test]
+    fn test_profile() {
+        let mut t = Interpreter::new(COUNTDOWN, &[]);
+        t.start_profile();
+        assert_eq!(t.run_to_halt(), Ok(vec![3, 2, 1]));
+        let profile = t.take_profile().unwrap();
+        assert_eq!(profile.instructions, 10);
+        assert_eq!(profile.opcodes.get(&1), Some(&3));
+        assert_eq!(profile.opcodes.get(&99), Some(&1));
+        assert_eq!(
+            profile.branches.get(&6),
+            Some(&BranchCount {
+                taken: 2,
+                not_taken: 1
+            })
+        );
+        let hot_spots: Vec<usize> = profile.hot_spots().iter().map(|&(a, _)| a).collect();
+        assert_eq!(hot_spots, vec![0, 2, 6, 9]);
+        assert!(t.profile().is_none());
+    }
+
+    #[test]
+    fn test_jump_to_next_instruction() {
+        let mut t = Interpreter::new("1105,1,3,99", &[]);
+        t.start_profile();
+        t.run_to_halt().unwrap();
+        assert_eq!(
+            t.profile().unwrap().branches.get(&0),
+            Some(&BranchCount {
+                taken: 1,
+                not_taken: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let mut t = Interpreter::new(COUNTDOWN, &[]);
+        t.start_profile();
+        t.run_to_halt().unwrap();
+        assert_eq!(
+            t.profile().unwrap().report(2),
+            "instructions: 10\n\
+             \n\
+             hot spots:\n  address       count       %  opcode\n\
+             \x20       0           3  30.00%  out\n\
+             \x20       2           3  30.00%  add\n\
+             \n\
+             opcodes:\n  opcode        count       %\n\
+             \x20 add               3  30.00%\n\
+             \x20 out               3  30.00%\n\
+             \x20 jnz               3  30.00%\n\
+             \x20 hlt               1  10.00%\n\
+             \n\
+             branches:\n  address       taken   not taken   taken%\n\
+             \x20       6           2           1   66.67%\n"
+        );
+    }
+}
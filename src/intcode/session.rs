@@ -0,0 +1,191 @@
+use super::{IntcodeError, Interpreter, RunOutput, Word};
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Event<W = i64> {
+    Input(W),
+    Output(W),
+    Halt,
+}
+
+impl<W: Word> fmt::Display for Event<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Input(value) => write!(f, "in {}", value),
+            Event::Output(value) => write!(f, "out {}", value),
+            Event::Halt => write!(f, "halt"),
+        }
+    }
+}
+
+// Input/output tape of a program run, in the order the program read and
+// wrote the values. Stored as one event per line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Session<W = i64> {
+    pub events: Vec<Event<W>>,
+}
+
+impl<W: Word> fmt::Display for Session<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in self.events.iter() {
+            writeln!(f, "{}", event)?;
+        }
+        Ok(())
+    }
+}
+
+// Point where a replayed program stopped following the recorded session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    // Index of the first event that didn't match.
+    pub index: usize,
+    pub expected: Event,
+    // What the program did instead, input requests show up as NeedsInput.
+    pub actual: Result<RunOutput, IntcodeError>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "event {}: expected '{}'", self.index, self.expected)?;
+        match self.actual {
+            Ok(RunOutput::NeedsInput) => write!(f, ", program requested input"),
+            Ok(RunOutput::Output(value)) => write!(f, ", program output {}", value),
+            Ok(RunOutput::Halt) => write!(f, ", program halted"),
+            Ok(RunOutput::Watch(hit)) => write!(f, ", program paused on {}", hit),
+            Err(err) => write!(f, ", program failed: {}", err),
+        }
+    }
+}
+
+impl Session {
+    pub fn parse(s: &str) -> Result<Session, String> {
+        let mut events = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let value = || {
+                parts[1]
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid value '{}'", i + 1, parts[1]))
+            };
+            let event = match parts.as_slice() {
+                [] => continue,
+                ["in", _] => Event::Input(value()?),
+                ["out", _] => Event::Output(value()?),
+                ["halt"] => Event::Halt,
+                _ => return Err(format!("line {}: invalid event '{}'", i + 1, line)),
+            };
+            events.push(event);
+        }
+        Ok(Session { events })
+    }
+
+    pub fn load(path: &str) -> io::Result<Session> {
+        Session::parse(&fs::read_to_string(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    // Run the program against the session, feeding recorded input when the
+    // program asks for it and comparing its output. Replay stops at the end of
+    // the session, or when the program halts if the halt was recorded.
+    pub fn replay(&self, program: &str) -> Result<(), Divergence> {
+        let mut interpreter = match Interpreter::parse(program, &[]) {
+            Ok(interpreter) => interpreter,
+            Err(err) => {
+                return match self.events.first() {
+                    Some(&expected) => Err(Divergence {
+                        index: 0,
+                        expected,
+                        actual: Err(err),
+                    }),
+                    None => Ok(()),
+                }
+            }
+        };
+        for (index, &expected) in self.events.iter().enumerate() {
+            let actual = interpreter.run();
+            match (expected, actual) {
+                (Event::Input(value), Ok(RunOutput::NeedsInput)) => interpreter.put_input(value),
+                (Event::Output(a), Ok(RunOutput::Output(b))) if a == b => {}
+                (Event::Halt, Ok(RunOutput::Halt)) => {}
+                _ => {
+                    return Err(Divergence {
+                        index,
+                        expected,
+                        actual,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Outputs double of each input until zero is read.
+    const PROGRAM: &str = "3,16,1006,16,14,1002,16,2,17,4,17,1105,1,0,99,0,0,0";
+
+    fn record(program: &str, input: Vec<i64>) -> Session {
+        let mut input = input.into_iter();
+        let mut t = Interpreter::new(program, &[]);
+        t.start_recording();
+        t.attach_input(move || input.next());
+        t.run_to_halt().unwrap();
+        t.take_recording().unwrap()
+    }
+
+    #[test]
+    fn test_record() {
+        let session = record(PROGRAM, vec![3, 5, 0]);
+        assert_eq!(
+            session.to_string(),
+            "in 3\nout 6\nin 5\nout 10\nin 0\nhalt\n"
+        );
+        assert_eq!(Session::parse(&session.to_string()), Ok(session));
+    }
+
+    #[test]
+    fn test_replay() {
+        let session = record(PROGRAM, vec![3, 5, 0]);
+        assert_eq!(session.replay(PROGRAM), Ok(()));
+
+        // Triple instead of double.
+        let changed = PROGRAM.replace("1002,16,2", "1002,16,3");
+        assert_eq!(
+            session.replay(&changed),
+            Err(Divergence {
+                index: 1,
+                expected: Event::Output(6),
+                actual: Ok(RunOutput::Output(9)),
+            })
+        );
+        assert_eq!(
+            session.replay(&changed).unwrap_err().to_string(),
+            "event 1: expected 'out 6', program output 9"
+        );
+        assert_eq!(
+            session.replay("").unwrap_err().to_string(),
+            "event 0: expected 'in 3', program failed: invalid value in cell 0 of program"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Session::parse("in 1\nout x"),
+            Err("line 2: invalid value 'x'".to_string())
+        );
+        assert_eq!(
+            Session::parse("jump 4"),
+            Err("line 1: invalid event 'jump 4'".to_string())
+        );
+    }
+}
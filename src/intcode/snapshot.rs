@@ -0,0 +1,154 @@
+use super::{Arithmetic, Interpreter, Memory};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+const HEADER: &str = "intcode-snapshot 1";
+
+fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+    values.map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn parse_list<T: FromStr>(key: &str, s: &str) -> Result<Vec<T>, String> {
+    if s.is_empty() {
+        return Ok(vec![]);
+    }
+    s.split(',')
+        .map(|v| {
+            v.parse()
+                .map_err(|_| format!("invalid {} value '{}'", key, v))
+        })
+        .collect()
+}
+
+fn parse_value<T: FromStr>(key: &str, s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid {} '{}'", key, s))
+}
+
+// Snapshots hold the memory, registers, pending input, memory limit and
+// arithmetic as `key value` lines. Attached devices, tracing and recording
+// are not saved.
+impl Interpreter {
+    pub fn snapshot(&self) -> String {
+        let mut s = String::new();
+        writeln!(s, "{}", HEADER).unwrap();
+        writeln!(s, "insp {}", self.insp).unwrap();
+        writeln!(s, "relative_base {}", self.relative_base).unwrap();
+        if let Some(limit) = self.memory_limit {
+            writeln!(s, "memory_limit {}", limit).unwrap();
+        }
+        if self.arithmetic != Arithmetic::default() {
+            writeln!(s, "arithmetic {}", self.arithmetic).unwrap();
+        }
+        writeln!(s, "input {}", join(self.input.iter())).unwrap();
+        writeln!(s, "memory {}", join(self.memory.dense_cells().iter())).unwrap();
+        let sparse = self
+            .memory
+            .sparse_cells()
+            .into_iter()
+            .map(|(address, value)| format!("{}:{}", address, value));
+        writeln!(s, "sparse {}", join(sparse)).unwrap();
+        s
+    }
+
+    pub fn restore(snapshot: &str) -> Result<Interpreter, String> {
+        let mut lines = snapshot.lines();
+        if lines.next() != Some(HEADER) {
+            return Err("not an intcode snapshot".to_string());
+        }
+
+        let mut interpreter = Interpreter::from_memory(Memory::default(), &[]);
+        let mut dense = vec![];
+        let mut sparse = HashMap::new();
+        for line in lines {
+            let (key, value) = match line.find(' ') {
+                Some(i) => (&line[..i], &line[i + 1..]),
+                None => (line, ""),
+            };
+            match key {
+                "insp" => interpreter.insp = parse_value(key, value)?,
+                "relative_base" => interpreter.relative_base = parse_value(key, value)?,
+                "memory_limit" => interpreter.memory_limit = Some(parse_value(key, value)?),
+                "arithmetic" => interpreter.arithmetic = parse_value(key, value)?,
+                "input" => interpreter.input = parse_list(key, value)?.into(),
+                "memory" => dense = parse_list(key, value)?,
+                "sparse" => {
+                    for cell in parse_list::<String>(key, value)? {
+                        let mut parts = cell.splitn(2, ':');
+                        let address = parse_value(key, parts.next().unwrap())?;
+                        let value = parse_value(key, parts.next().unwrap_or(""))?;
+                        sparse.insert(address, value);
+                    }
+                }
+                "" => {}
+                _ => return Err(format!("unknown snapshot field '{}'", key)),
+            }
+        }
+        interpreter.memory = Memory::from_parts(dense, sparse);
+        Ok(interpreter)
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.snapshot())
+    }
+
+    pub fn load_snapshot(path: &str) -> io::Result<Interpreter> {
+        Interpreter::restore(&fs::read_to_string(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::RunOutput;
+
+    #[test]
+    fn test_snapshot() {
+        let program = "109,7,203,0,4,7,99";
+        let mut t = Interpreter::new(program, &[]);
+        t.set_memory_limit(Some(1 << 50));
+        t.set_arithmetic(Arithmetic::Wrapping);
+        t.write_memory(1 << 40, -5);
+        assert_eq!(t.run(), Ok(RunOutput::NeedsInput));
+        t.put_input(12);
+        t.put_input(13);
+
+        let snapshot = t.snapshot();
+        assert_eq!(
+            snapshot,
+            "intcode-snapshot 1\n\
+             insp 2\n\
+             relative_base 7\n\
+             memory_limit 1125899906842624\n\
+             arithmetic wrapping\n\
+             input 12,13\n\
+             memory 109,7,203,0,4,7,99\n\
+             sparse 1099511627776:-5\n"
+        );
+
+        let mut restored = Interpreter::restore(&snapshot).unwrap();
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(restored.run(), Ok(RunOutput::Output(12)));
+        assert_eq!(restored.pending_input(), &[13]);
+        assert_eq!(restored.read_memory(1 << 40), -5);
+    }
+
+    #[test]
+    fn test_restore_errors() {
+        assert_eq!(
+            Interpreter::restore("insp 0").unwrap_err(),
+            "not an intcode snapshot"
+        );
+        assert_eq!(
+            Interpreter::restore("intcode-snapshot 1\ninsp x").unwrap_err(),
+            "invalid insp 'x'"
+        );
+        assert_eq!(
+            Interpreter::restore("intcode-snapshot 1\nfoo 1").unwrap_err(),
+            "unknown snapshot field 'foo'"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use super::Word;
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+
+// One executed instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry<W = i64> {
+    pub insp: usize,
+    pub opcode: i64,
+    // Mode digit of each parameter.
+    pub modes: Vec<i64>,
+    // Values of the parameters the instruction reads, with modes applied.
+    pub operands: Vec<W>,
+    // Address and value written by the instruction.
+    pub write: Option<(usize, W)>,
+    // Relative base in effect when the instruction was executed.
+    pub relative_base: W,
+}
+
+fn join<T: fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl<W: Word> TraceEntry<W> {
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"insp\":{},\"opcode\":{},\"modes\":[{}],\"operands\":[{}],\"write\":",
+            self.insp,
+            self.opcode,
+            join(&self.modes),
+            join(&self.operands)
+        );
+        match &self.write {
+            Some((address, value)) => {
+                write!(json, "{{\"address\":{},\"value\":{}}}", address, value).unwrap()
+            }
+            None => json.push_str("null"),
+        }
+        write!(json, ",\"relative_base\":{}}}", self.relative_base).unwrap();
+        json
+    }
+}
+
+// Writes trace entries as JSON lines. Tracing stops at the first write error,
+// which is returned from finish.
+pub struct Tracer {
+    writer: Box<dyn Write>,
+    error: Option<io::Error>,
+}
+
+impl Tracer {
+    pub fn new<T: Write + 'static>(writer: T) -> Self {
+        Tracer {
+            writer: Box::new(writer),
+            error: None,
+        }
+    }
+
+    pub fn record<W: Word>(&mut self, entry: &TraceEntry<W>) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(err) = writeln!(self.writer, "{}", entry.to_json()) {
+            self.error = Some(err);
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let entry = TraceEntry {
+            insp: 4,
+            opcode: 1001,
+            modes: vec![0, 1, 0],
+            operands: vec![5, -1],
+            write: Some((100, 4)),
+            relative_base: 0,
+        };
+        assert_eq!(
+            entry.to_json(),
+            "{\"insp\":4,\"opcode\":1001,\"modes\":[0,1,0],\"operands\":[5,-1],\
+             \"write\":{\"address\":100,\"value\":4},\"relative_base\":0}"
+        );
+
+        let entry = TraceEntry {
+            insp: 15,
+            opcode: 99,
+            modes: vec![],
+            operands: vec![],
+            write: None,
+            relative_base: 3,
+        };
+        assert_eq!(
+            entry.to_json(),
+            "{\"insp\":15,\"opcode\":99,\"modes\":[],\"operands\":[],\"write\":null,\"relative_base\":3}"
+        );
+    }
+}
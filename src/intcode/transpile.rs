@@ -0,0 +1,355 @@
+use super::native::checksum;
+use super::{Cfg, Instruction, OpcodeMode};
+use std::collections::BTreeSet;
+use std::convert::TryInto;
+use std::fmt::Write as _;
+
+// Constant stored by an add or multiply of two immediates. Programs store
+// return addresses this way.
+fn stored_constant(instruction: &Instruction) -> Option<i64> {
+    let modes = instruction.modes();
+    if modes[0] != OpcodeMode::Immediate || modes[1] != OpcodeMode::Immediate {
+        return None;
+    }
+    let (a, b) = (instruction.params[0], instruction.params[1]);
+    match instruction.opcode.number {
+        1 => a.checked_add(b),
+        2 => a.checked_mul(b),
+        _ => None,
+    }
+}
+
+// Cfg that also starts from stored constants that are program addresses.
+// Constants that aren't code give blocks that never run.
+fn compiled_cfg(cells: &[i64]) -> Cfg {
+    let mut roots = vec![0];
+    loop {
+        let cfg = Cfg::build_with_roots(cells, &roots);
+        let constants: BTreeSet<usize> = cfg
+            .blocks
+            .values()
+            .flat_map(|block| block.instructions.iter())
+            .filter_map(stored_constant)
+            .filter_map(|constant| constant.try_into().ok())
+            .filter(|&address: &usize| address < cells.len() && !roots.contains(&address))
+            .collect();
+        if constants.is_empty() {
+            return cfg;
+        }
+        roots.extend(constants);
+    }
+}
+
+// Blocks of compiled code. Basic blocks are split before each input, where a
+// run resumes after NeedsInput, and after each output.
+fn units(cfg: &Cfg) -> Vec<Vec<Instruction>> {
+    let mut units = vec![];
+    for block in cfg.blocks.values() {
+        let mut unit: Vec<Instruction> = vec![];
+        for instruction in block.instructions.iter() {
+            if instruction.opcode.number == 3 && !unit.is_empty() {
+                units.push(std::mem::take(&mut unit));
+            }
+            unit.push(instruction.clone());
+            if instruction.opcode.number == 4 {
+                units.push(std::mem::take(&mut unit));
+            }
+        }
+        if !unit.is_empty() {
+            units.push(unit);
+        }
+    }
+    units
+}
+
+fn relative(param: i64) -> String {
+    match param {
+        0 => "m.relative_base".to_string(),
+        p => format!("m.relative_address(&{})?", p),
+    }
+}
+
+// Expression for the value of a parameter.
+fn read(param: i64, mode: OpcodeMode) -> String {
+    match mode {
+        OpcodeMode::Immediate => param.to_string(),
+        OpcodeMode::Positional => format!("m.load({})?", param),
+        OpcodeMode::Relative => format!("m.load({})?", relative(param)),
+    }
+}
+
+// Expression for the address of a written parameter.
+fn address(param: i64, mode: OpcodeMode) -> String {
+    match mode {
+        OpcodeMode::Relative => relative(param),
+        _ => param.to_string(),
+    }
+}
+
+// Store a value, leaving compiled code when the write hits an instruction.
+fn write(s: &mut String, instruction: &Instruction, value: &str) {
+    let param = instruction.params.len() - 1;
+    let target = address(instruction.params[param], instruction.modes()[param]);
+    writeln!(s, "    if is_code(m.save({}, {})?) {{", target, value).unwrap();
+    writeln!(s, "        m.insp = {};", instruction.next_address()).unwrap();
+    writeln!(s, "        return Ok(Exit::Fallback);").unwrap();
+    writeln!(s, "    }}").unwrap();
+}
+
+// Code for one instruction. The last instruction of a unit returns.
+fn instruction_code(s: &mut String, instruction: &Instruction, last: bool) {
+    let modes = instruction.modes();
+    let params = &instruction.params;
+    let next = instruction.next_address();
+    writeln!(s, "    // {}: {}", instruction.address, instruction).unwrap();
+    writeln!(s, "    m.insp = {};", instruction.address).unwrap();
+    match instruction.opcode.number {
+        1 | 2 | 7 | 8 => {
+            writeln!(s, "    let a: i64 = {};", read(params[0], modes[0])).unwrap();
+            writeln!(s, "    let b: i64 = {};", read(params[1], modes[1])).unwrap();
+            let value = match instruction.opcode.number {
+                1 => "m.add(&a, &b)?",
+                2 => "m.mul(&a, &b)?",
+                7 => "i64::from(a < b)",
+                _ => "i64::from(a == b)",
+            };
+            write(s, instruction, value);
+        }
+        3 => {
+            writeln!(s, "    let value = match m.peek_input() {{").unwrap();
+            writeln!(s, "        Some(value) => value,").unwrap();
+            writeln!(s, "        None => return Ok(Exit::NeedsInput),").unwrap();
+            writeln!(s, "    }};").unwrap();
+            let target = address(params[0], modes[0]);
+            writeln!(s, "    let address = m.save({}, value)?;", target).unwrap();
+            writeln!(s, "    m.consume_input();").unwrap();
+            writeln!(s, "    if is_code(address) {{").unwrap();
+            writeln!(s, "        m.insp = {};", next).unwrap();
+            writeln!(s, "        return Ok(Exit::Fallback);").unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+        4 => {
+            writeln!(s, "    let a: i64 = {};", read(params[0], modes[0])).unwrap();
+            writeln!(s, "    m.insp = {};", next).unwrap();
+            writeln!(s, "    if let Some(a) = m.send(a) {{").unwrap();
+            writeln!(s, "        return Ok(Exit::Output(a));").unwrap();
+            writeln!(s, "    }}").unwrap();
+        }
+        5 | 6 => {
+            let target = format!("m.jump_target(&{})?", read(params[1], modes[1]));
+            if modes[0] == OpcodeMode::Immediate {
+                let jumps = (params[0] != 0) == (instruction.opcode.number == 5);
+                if jumps {
+                    writeln!(s, "    m.insp = {};", target).unwrap();
+                } else {
+                    writeln!(s, "    m.insp = {};", next).unwrap();
+                }
+            } else {
+                let op = if instruction.opcode.number == 5 {
+                    "!="
+                } else {
+                    "=="
+                };
+                writeln!(s, "    let a: i64 = {};", read(params[0], modes[0])).unwrap();
+                writeln!(s, "    m.insp = if a {} 0 {{", op).unwrap();
+                writeln!(s, "        {}", target).unwrap();
+                writeln!(s, "    }} else {{").unwrap();
+                writeln!(s, "        {}", next).unwrap();
+                writeln!(s, "    }};").unwrap();
+            }
+            writeln!(s, "    Ok(Exit::Continue)").unwrap();
+            return;
+        }
+        9 => {
+            writeln!(s, "    let a: i64 = {};", read(params[0], modes[0])).unwrap();
+            writeln!(s, "    m.adjust_relative_base(&a)?;").unwrap();
+        }
+        _ => {
+            writeln!(s, "    m.halt();").unwrap();
+            writeln!(s, "    Ok(Exit::Halt)").unwrap();
+            return;
+        }
+    }
+    if last {
+        if instruction.opcode.number != 4 {
+            writeln!(s, "    m.insp = {};", next).unwrap();
+        }
+        writeln!(s, "    Ok(Exit::Continue)").unwrap();
+    }
+}
+
+// Address ranges of compiled instructions, merged where adjacent.
+fn code_ranges(units: &[Vec<Instruction>]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = units
+        .iter()
+        .flatten()
+        .map(|instruction| (instruction.address, instruction.next_address()))
+        .collect();
+    spans.sort_unstable();
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for (start, end) in spans {
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+// Rust module running the program with one function per block and a
+// COMPILED constant for Native. Code reachable as in Cfg or from stored
+// constants is compiled, a jump anywhere else or a write to compiled code falls back to the interpreter.
+// The module has to live below intcode, since the code uses interpreter
+// internals.
+pub fn transpile(cells: &[i64], source: &str) -> String {
+    let units = units(&compiled_cfg(cells));
+    let ranges = code_ranges(&units);
+    let mut s = String::new();
+    writeln!(
+        s,
+        "// Compiled from {} by the transpile tool, do not edit.",
+        source
+    )
+    .unwrap();
+    writeln!(s, "use crate::intcode::native::{{Compiled, Exit}};").unwrap();
+    writeln!(s, "use crate::intcode::{{IntcodeError, Interpreter}};").unwrap();
+    writeln!(s).unwrap();
+    writeln!(s, "pub const COMPILED: Compiled = Compiled {{").unwrap();
+    writeln!(s, "    len: {},", cells.len()).unwrap();
+    let code: Vec<String> = ranges
+        .iter()
+        .map(|(start, end)| format!("({}, {})", start, end))
+        .collect();
+    writeln!(s, "    code: &[{}],", code.join(", ")).unwrap();
+    let sum = checksum(&ranges, |address| cells[address]);
+    writeln!(s, "    checksum: {:#018x},", sum).unwrap();
+    writeln!(s, "    dispatch,").unwrap();
+    writeln!(s, "}};").unwrap();
+    writeln!(s).unwrap();
+    writeln!(
+        s,
+        "pub fn dispatch(m: &mut Interpreter) -> Result<Exit, IntcodeError> {{"
+    )
+    .unwrap();
+    writeln!(s, "    match m.insp {{").unwrap();
+    for unit in units.iter() {
+        let start = unit[0].address;
+        writeln!(s, "        {} => b{}(m),", start, start).unwrap();
+    }
+    writeln!(s, "        _ => Ok(Exit::Fallback),").unwrap();
+    writeln!(s, "    }}").unwrap();
+    writeln!(s, "}}").unwrap();
+    writeln!(s).unwrap();
+
+    writeln!(s, "fn is_code(address: usize) -> bool {{").unwrap();
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|(start, end)| format!("{}..={}", start, end - 1))
+        .collect();
+    if ranges.is_empty() {
+        writeln!(s, "    let _ = address;").unwrap();
+        writeln!(s, "    false").unwrap();
+    } else {
+        writeln!(s, "    matches!(").unwrap();
+        writeln!(s, "        address,").unwrap();
+        writeln!(s, "        {}", ranges.join("\n        | ")).unwrap();
+        writeln!(s, "    )").unwrap();
+    }
+    writeln!(s, "}}").unwrap();
+
+    for unit in units.iter() {
+        writeln!(s).unwrap();
+        writeln!(
+            s,
+            "fn b{}(m: &mut Interpreter) -> Result<Exit, IntcodeError> {{",
+            unit[0].address
+        )
+        .unwrap();
+        for (i, instruction) in unit.iter().enumerate() {
+            instruction_code(&mut s, instruction, i + 1 == unit.len());
+        }
+        writeln!(s, "}}").unwrap();
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::generated::{day9, echo};
+    use crate::intcode::{parse_program, Interpreter, Native, RunOutput};
+
+    // Outputs its input until it is 0, the input is written into the output
+    // instruction at 2.
+    const ECHO: &str = "3,3,104,0,1005,3,0,99";
+
+    fn day9_program() -> String {
+        std::fs::read_to_string("inputs/day9.txt").unwrap()
+    }
+
+    #[test]
+    fn test_generated_up_to_date() {
+        let cells = parse_program(&day9_program()).unwrap();
+        assert_eq!(
+            transpile(&cells, "inputs/day9.txt"),
+            include_str!("generated/day9.rs")
+        );
+        assert_eq!(
+            transpile(&parse_program(ECHO).unwrap(), "the ECHO test program"),
+            include_str!("generated/echo.rs")
+        );
+    }
+
+    #[test]
+    fn test_same_results() {
+        let program = day9_program();
+        for &input in [1, 2].iter() {
+            let mut native = Native::new(Interpreter::new(&program, &[input]), day9::COMPILED);
+            let mut plain = Interpreter::new(&program, &[input]);
+            assert_eq!(native.run_to_halt(), plain.run_to_halt());
+            assert!(!native.is_fallback());
+            assert_eq!(native.interpreter().snapshot(), plain.snapshot());
+        }
+    }
+
+    #[test]
+    fn test_needs_input() {
+        let program = day9_program();
+        let mut native = Native::new(Interpreter::new(&program, &[]), day9::COMPILED);
+        assert_eq!(native.run(), Ok(RunOutput::NeedsInput));
+        native.put_input(1);
+        assert_eq!(native.run_to_halt().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_fallback() {
+        let program = ECHO;
+        let mut native = Native::new(Interpreter::new(program, &[5, 0]), echo::COMPILED);
+        assert_eq!(native.run_to_halt(), Ok(vec![5, 0]));
+        assert!(native.is_fallback());
+    }
+
+    #[test]
+    fn test_program_mismatch() {
+        // Jumps to the halt instead of back to the start once patched.
+        let mut patched = Interpreter::new(ECHO, &[5, 0]);
+        patched.write_memory(6, 7);
+        let mut native = Native::new(patched, echo::COMPILED);
+        assert!(native.is_fallback());
+        assert_eq!(native.run_to_halt(), Ok(vec![5]));
+
+        let native = Native::new(Interpreter::new(ECHO, &[]), day9::COMPILED);
+        assert!(native.is_fallback());
+        let native = Native::new(Interpreter::new(&day9_program(), &[]), day9::COMPILED);
+        assert!(!native.is_fallback());
+    }
+
+    #[test]
+    fn test_code_ranges() {
+        let cells = parse_program(ECHO).unwrap();
+        let units = units(&compiled_cfg(&cells));
+        let starts: Vec<usize> = units.iter().map(|unit| unit[0].address).collect();
+        assert_eq!(starts, vec![0, 4, 7]);
+        assert_eq!(code_ranges(&units), vec![(0, 8)]);
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Interpreter, Word};
+use std::collections::VecDeque;
+
+// State overwritten by one executed instruction.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UndoEntry<W = i64> {
+    pub insp: usize,
+    pub relative_base: W,
+    // Written address and its value before the write.
+    pub write: Option<(usize, W)>,
+    // Instruction consumed an input value, which is the value it wrote.
+    pub input: bool,
+}
+
+// Undo entries of the most recently executed instructions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoLog<W = i64> {
+    entries: VecDeque<UndoEntry<W>>,
+    capacity: usize,
+}
+
+impl<W> UndoLog<W> {
+    pub fn new(capacity: usize) -> Self {
+        UndoLog {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, entry: UndoEntry<W>) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoEntry<W>> {
+        self.entries.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    // Keep undo entries for the last `capacity` instructions so they can be
+    // reverted with step_back. None turns the log off.
+    pub fn set_undo_log(&mut self, capacity: Option<usize>) {
+        self.undo_log = capacity.map(UndoLog::new);
+    }
+
+    // Number of instructions that can be stepped back.
+    pub fn undo_depth(&self) -> usize {
+        self.undo_log.as_ref().map_or(0, |log| log.len())
+    }
+
+    // Revert the last n executed instructions. Consumed input is put back to
+    // the input queue, output can't be taken back. Returns the number of
+    // instructions reverted, which is less than n if the log runs out.
+    pub fn step_back(&mut self, n: usize) -> usize {
+        for i in 0..n {
+            let entry = match self.undo_log.as_mut().and_then(|log| log.pop()) {
+                Some(entry) => entry,
+                None => return i,
+            };
+            if let Some((address, value)) = entry.write {
+                if entry.input {
+                    self.input.push_front(self.read_memory(address));
+                }
+                self.store(address, value);
+            }
+            self.insp = entry.insp;
+            self.relative_base = entry.relative_base;
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::RunOutput;
+
+    #[test]
+    fn test_step_back() {
+        let program = "3,11,109,3,1,11,11,12,4,12,99,0,0";
+        let mut t = Interpreter::new(program, &[21]);
+        t.set_undo_log(Some(100));
+        let initial = t.snapshot();
+
+        assert_eq!(t.run(), Ok(RunOutput::Output(42)));
+        assert_eq!(t.undo_depth(), 4);
+        assert_eq!(t.step_back(1), 1);
+        assert_eq!(t.insp(), 8);
+        assert_eq!(t.read_memory(12), 42);
+
+        assert_eq!(t.step_back(1), 1);
+        assert_eq!(t.insp(), 4);
+        assert_eq!(t.read_memory(12), 0);
+        assert_eq!(t.relative_base(), 3);
+
+        assert_eq!(t.step_back(10), 2);
+        assert_eq!(t.snapshot(), initial);
+        assert_eq!(t.run_to_halt(), Ok(vec![42]));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut t = Interpreter::new("1101,1,1,9,1101,2,2,10,99,0,0", &[]);
+        t.set_undo_log(Some(1));
+        assert_eq!(t.run(), Ok(RunOutput::Halt));
+        assert_eq!(t.step_back(3), 1);
+        assert_eq!(t.insp(), 4);
+        assert_eq!(t.read_memory(9), 2);
+        assert_eq!(t.read_memory(10), 0);
+    }
+}
@@ -0,0 +1,139 @@
+use super::{Interpreter, Word};
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl WatchKind {
+    fn matches(&self, access: Access) -> bool {
+        matches!(
+            (self, access),
+            (WatchKind::ReadWrite, _)
+                | (WatchKind::Read, Access::Read)
+                | (WatchKind::Write, Access::Write)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watchpoint {
+    pub range: Range<usize>,
+    pub kind: WatchKind,
+}
+
+// Access to a watched address by the instruction at insp. For reads the old
+// and new values are the same. Output of the instruction is returned here
+// instead of as RunOutput::Output.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WatchHit<W = i64> {
+    pub insp: usize,
+    pub opcode: i64,
+    pub access: Access,
+    pub address: usize,
+    pub old: W,
+    pub new: W,
+    pub output: Option<W>,
+}
+
+impl<W: fmt::Display> fmt::Display for WatchHit<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.access {
+            Access::Read => write!(
+                f,
+                "read of {} by opcode {} at {}: {}",
+                self.address, self.opcode, self.insp, self.old
+            ),
+            Access::Write => write!(
+                f,
+                "write to {} by opcode {} at {}: {} -> {}",
+                self.address, self.opcode, self.insp, self.old, self.new
+            ),
+        }
+    }
+}
+
+impl<W: Word> Interpreter<W> {
+    // Pause with RunOutput::Watch after an instruction accesses an address in
+    // the range. Only parameter accesses are watched, not instruction fetches.
+    pub fn watch(&mut self, range: Range<usize>, kind: WatchKind) {
+        self.watchpoints.push(Watchpoint { range, kind });
+    }
+
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    pub(super) fn is_watched(&self, address: usize, access: Access) -> bool {
+        self.watchpoints
+            .iter()
+            .any(|w| w.range.contains(&address) && w.kind.matches(access))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::fixtures::COUNTDOWN;
+    use crate::intcode::RunOutput;
+
+    #[test]
+    fn test_write_watch() {
+        let mut t = Interpreter::new(COUNTDOWN, &[]);
+        t.watch(12..13, WatchKind::Write);
+        assert_eq!(t.run(), Ok(RunOutput::Output(3)));
+        let hit = WatchHit {
+            insp: 2,
+            opcode: 1001,
+            access: Access::Write,
+            address: 12,
+            old: 3,
+            new: 2,
+            output: None,
+        };
+        assert_eq!(t.run(), Ok(RunOutput::Watch(hit)));
+        assert_eq!(hit.to_string(), "write to 12 by opcode 1001 at 2: 3 -> 2");
+        assert_eq!(t.insp(), 6);
+        assert_eq!(t.run(), Ok(RunOutput::Output(2)));
+    }
+
+    #[test]
+    fn test_read_watch_on_output() {
+        let mut t = Interpreter::new(COUNTDOWN, &[]);
+        t.watch(10..20, WatchKind::Read);
+        let hit = WatchHit {
+            insp: 0,
+            opcode: 4,
+            access: Access::Read,
+            address: 12,
+            old: 3,
+            new: 3,
+            output: Some(3),
+        };
+        assert_eq!(t.run(), Ok(RunOutput::Watch(hit)));
+
+        t.clear_watchpoints();
+        assert_eq!(t.run_to_halt(), Ok(vec![2, 1]));
+    }
+
+    #[test]
+    fn test_run_to_halt_ignores_watches() {
+        let mut t = Interpreter::new(COUNTDOWN, &[]);
+        t.watch(0..100, WatchKind::ReadWrite);
+        assert_eq!(t.run_to_halt(), Ok(vec![3, 2, 1]));
+    }
+}
@@ -0,0 +1,358 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+// Integer type of memory cells in an Interpreter. The default value is zero.
+pub trait Word: Clone + Ord + Default + fmt::Debug + fmt::Display + FromStr {
+    fn from_bool(value: bool) -> Self;
+
+    // None if the value doesn't fit in an i64.
+    fn to_i64(&self) -> Option<i64>;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    fn wrapping_add(&self, other: &Self) -> Self;
+    fn wrapping_mul(&self, other: &Self) -> Self;
+    fn saturating_add(&self, other: &Self) -> Self;
+    fn saturating_mul(&self, other: &Self) -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // Value clamped to an i64, for error messages.
+    fn saturate(&self) -> i64 {
+        match self.to_i64() {
+            Some(value) => value,
+            None if *self < Self::default() => i64::MIN,
+            None => i64::MAX,
+        }
+    }
+}
+
+macro_rules! primitive_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                fn from_bool(value: bool) -> Self {
+                    <$t>::from(value)
+                }
+
+                fn to_i64(&self) -> Option<i64> {
+                    i64::try_from(*self).ok()
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn wrapping_add(&self, other: &Self) -> Self {
+                    <$t>::wrapping_add(*self, *other)
+                }
+
+                fn wrapping_mul(&self, other: &Self) -> Self {
+                    <$t>::wrapping_mul(*self, *other)
+                }
+
+                fn saturating_add(&self, other: &Self) -> Self {
+                    <$t>::saturating_add(*self, *other)
+                }
+
+                fn saturating_mul(&self, other: &Self) -> Self {
+                    <$t>::saturating_mul(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+primitive_word!(i32, i64, i128);
+
+// Arbitrary precision integer. The magnitude is stored in base 2^32, least
+// significant digit first and without leading zeros, so zero has no digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+fn trim(mut magnitude: Vec<u32>) -> Vec<u32> {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+    magnitude
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let digit = u64::from(*a.get(i).unwrap_or(&0)) + u64::from(*b.get(i).unwrap_or(&0)) + carry;
+        sum.push(digit as u32);
+        carry = digit >> 32;
+    }
+    sum.push(carry as u32);
+    trim(sum)
+}
+
+// a - b where a >= b.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &digit) in a.iter().enumerate() {
+        let subtrahend = i64::from(*b.get(i).unwrap_or(&0)) + borrow;
+        let mut digit = i64::from(digit) - subtrahend;
+        borrow = 0;
+        if digit < 0 {
+            digit += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(digit as u32);
+    }
+    trim(difference)
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let digit = u64::from(x) * u64::from(y) + u64::from(product[i + j]) + carry;
+            product[i + j] = digit as u32;
+            carry = digit >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trim(product)
+}
+
+// Quotient and remainder of a division by a single digit.
+fn div_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for (i, &digit) in a.iter().enumerate().rev() {
+        let value = (remainder << 32) | u64::from(digit);
+        quotient[i] = (value / u64::from(divisor)) as u32;
+        remainder = value % u64::from(divisor);
+    }
+    (trim(quotient), remainder as u32)
+}
+
+impl BigInt {
+    fn new(negative: bool, magnitude: Vec<u32>) -> Self {
+        let magnitude = trim(magnitude);
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        let abs = value.unsigned_abs();
+        BigInt::new(value < 0, vec![abs as u32, (abs >> 32) as u32])
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.magnitude, &other.magnitude),
+            (true, true) => compare_magnitude(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.magnitude.is_empty() {
+            return write!(f, "0");
+        }
+        // Digits in chunks of nine, least significant first.
+        let mut chunks = vec![];
+        let mut rest = self.magnitude.clone();
+        while !rest.is_empty() {
+            let (quotient, remainder) = div_small(&rest, 1_000_000_000);
+            chunks.push(remainder);
+            rest = quotient;
+        }
+        let mut s = String::new();
+        if self.negative {
+            s.push('-');
+        }
+        s.push_str(&chunks.pop().unwrap().to_string());
+        for chunk in chunks.iter().rev() {
+            s.push_str(&format!("{:09}", chunk));
+        }
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("invalid integer '{}'", s));
+        }
+        let mut magnitude = vec![];
+        for b in digits.bytes() {
+            magnitude = mul_magnitude(&magnitude, &[10]);
+            magnitude = add_magnitude(&magnitude, &[u32::from(b - b'0')]);
+        }
+        Ok(BigInt::new(negative, magnitude))
+    }
+}
+
+// Results are exact, so every policy gives the same result.
+impl Word for BigInt {
+    fn from_bool(value: bool) -> Self {
+        BigInt::from(i64::from(value))
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let abs = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0i128, |value, &digit| (value << 32) | i128::from(digit));
+        i64::try_from(if self.negative { -abs } else { abs }).ok()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        if self.negative == other.negative {
+            return Some(BigInt::new(
+                self.negative,
+                add_magnitude(&self.magnitude, &other.magnitude),
+            ));
+        }
+        Some(match compare_magnitude(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::new(
+                other.negative,
+                sub_magnitude(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::new(
+                self.negative,
+                sub_magnitude(&self.magnitude, &other.magnitude),
+            ),
+        })
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(BigInt::new(
+            self.negative != other.negative,
+            mul_magnitude(&self.magnitude, &other.magnitude),
+        ))
+    }
+
+    fn wrapping_add(&self, other: &Self) -> Self {
+        self.checked_add(other).unwrap()
+    }
+
+    fn wrapping_mul(&self, other: &Self) -> Self {
+        self.checked_mul(other).unwrap()
+    }
+
+    fn saturating_add(&self, other: &Self) -> Self {
+        self.checked_add(other).unwrap()
+    }
+
+    fn saturating_mul(&self, other: &Self) -> Self {
+        self.checked_mul(other).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for &s in [
+            "0",
+            "7",
+            "-7",
+            "4294967296",
+            "-9223372036854775808",
+            "123456789012345678901234567890",
+        ]
+        .iter()
+        {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("-0"), BigInt::default());
+        assert_eq!(big("+000012").to_string(), "12");
+        assert!("1-2".parse::<BigInt>().is_err());
+        assert!("".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = big("9223372036854775807");
+        assert_eq!(
+            a.checked_mul(&a).unwrap().to_string(),
+            "85070591730234615847396907784232501249"
+        );
+        assert_eq!(
+            a.checked_add(&big("1")).unwrap().to_string(),
+            "9223372036854775808"
+        );
+        assert_eq!(big("5").checked_add(&big("-12")), Some(big("-7")));
+        assert_eq!(big("-5").checked_add(&big("5")), Some(BigInt::default()));
+        assert_eq!(
+            big("-4294967296").checked_mul(&big("-3")),
+            Some(big("12884901888"))
+        );
+    }
+
+    #[test]
+    fn test_order_and_conversion() {
+        let mut values = [
+            big("10"),
+            big("-3"),
+            big("0"),
+            big("-40000000000"),
+            big("2"),
+        ];
+        values.sort();
+        let sorted: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(sorted, vec!["-40000000000", "-3", "0", "2", "10"]);
+
+        assert_eq!(BigInt::from(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert_eq!(big("9223372036854775808").saturate(), i64::MAX);
+        assert_eq!(300i128.to_i64(), Some(300));
+        assert_eq!(i128::MIN.saturate(), i64::MIN);
+    }
+}
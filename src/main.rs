@@ -1,7 +1,5 @@
 extern crate regex;
-#[macro_use]
 extern crate lazy_static;
-#[macro_use]
 extern crate itertools;
 
 mod day1;
@@ -30,6 +28,9 @@ mod day7;
 mod day8;
 mod day9;
 
+use aoc_2019::intcode;
+use itertools::Itertools;
+
 pub fn read_and_parse_input<T>(file: &str) -> Vec<T>
 where
     T: std::str::FromStr,
@@ -49,12 +50,194 @@ pub fn read_input(file: &str) -> String {
         .to_string()
 }
 
+// Output of a program run with cells of type W, or why it couldn't run.
+fn run_with_word<W: intcode::Word>(program: &str, input: &[String]) -> String {
+    let cells = match intcode::parse_words::<W>(program) {
+        Ok(cells) => cells,
+        Err(err) => return format!("error: {}", err),
+    };
+    let input: Vec<W> = match input.iter().map(|v| v.parse().ok()).collect() {
+        Some(input) => input,
+        None => return "input does not fit".to_string(),
+    };
+    let memory = intcode::Memory::new(cells);
+    match intcode::Interpreter::from_memory(memory, &input).run_to_halt() {
+        Ok(output) => output.iter().join(","),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+// Intcode tools take a program file instead of a day number. Returns false
+// if the name is not a tool.
+fn run_intcode_tool(tool: &str, args: &[String]) -> bool {
+    let read_program = || {
+        let file = args.first().expect("missing program file");
+        std::fs::read_to_string(file).unwrap()
+    };
+
+    match tool {
+        "disasm" => match intcode::disassemble(&read_program()) {
+            Ok(listing) => print!("{}", listing),
+            Err(err) => eprintln!("{}", err),
+        },
+        "asm" => match intcode::assemble(&read_program()) {
+            Ok(program) => println!("{}", program),
+            Err(err) => eprintln!("{}", err),
+        },
+        "cfg" => {
+            let dot_file = args.get(1).expect("missing dot file");
+            let cfg = match intcode::Cfg::from_program(&read_program()) {
+                Ok(cfg) => cfg,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            std::fs::write(dot_file, cfg.to_dot()).unwrap();
+            println!("{} basic blocks", cfg.blocks.len());
+        }
+        "decompile" => match intcode::decompile(&read_program()) {
+            Ok(source) => print!("{}", source),
+            Err(err) => eprintln!("{}", err),
+        },
+        "words" => {
+            let program = read_program();
+            let input = &args[1..];
+            println!("i32     {}", run_with_word::<i32>(&program, input));
+            println!("i64     {}", run_with_word::<i64>(&program, input));
+            println!("i128    {}", run_with_word::<i128>(&program, input));
+            println!(
+                "bigint  {}",
+                run_with_word::<intcode::BigInt>(&program, input)
+            );
+        }
+        "transpile" => {
+            let rust_file = args.get(1).expect("missing rust file");
+            let cells = match intcode::parse_program(&read_program()) {
+                Ok(cells) => cells,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            std::fs::write(rust_file, intcode::transpile(&cells, &args[0])).unwrap();
+        }
+        "bench" => {
+            // Day 9 part 2 on the interpreter, with the instruction cache and
+            // compiled by transpile.
+            let runs: u32 = args
+                .first()
+                .map_or(10, |v| v.parse().expect("runs must be integer"));
+            let program = read_input("day9.txt");
+            let time = |name: &str, run: &dyn Fn() -> Vec<i64>| {
+                let start = std::time::Instant::now();
+                let output: Vec<Vec<i64>> = (0..runs).map(|_| run()).collect();
+                println!(
+                    "{:<12}{:>10.2?}  {:?}",
+                    name,
+                    start.elapsed() / runs,
+                    output[0]
+                );
+            };
+            time("interpreter", &|| {
+                intcode::Interpreter::new(&program, &[2])
+                    .run_to_halt()
+                    .unwrap()
+            });
+            time("cache", &|| {
+                let mut interpreter = intcode::Interpreter::new(&program, &[2]);
+                interpreter.set_instruction_cache(true);
+                interpreter.run_to_halt().unwrap()
+            });
+            time("native", &|| {
+                let interpreter = intcode::Interpreter::new(&program, &[2]);
+                intcode::Native::new(interpreter, intcode::generated::day9::COMPILED)
+                    .run_to_halt()
+                    .unwrap()
+            });
+        }
+        "debug" => {
+            let program = match intcode::Interpreter::parse(&read_program(), &[]) {
+                Ok(program) => program,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            intcode::Debugger::new(program).repl();
+        }
+        "trace" => {
+            let trace_file = args.get(1).expect("missing trace file");
+            let input: Vec<i64> = args[2..]
+                .iter()
+                .map(|v| v.parse().expect("input must be integer"))
+                .collect();
+            let mut program = match intcode::Interpreter::parse(&read_program(), &input) {
+                Ok(program) => program,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            let writer = std::io::BufWriter::new(std::fs::File::create(trace_file).unwrap());
+            program.start_trace(writer);
+            println!("output: {:?}", program.run_to_halt());
+            program.finish_trace().unwrap();
+        }
+        "profile" => {
+            let input: Vec<i64> = args[1..]
+                .iter()
+                .map(|v| v.parse().expect("input must be integer"))
+                .collect();
+            let mut program = match intcode::Interpreter::parse(&read_program(), &input) {
+                Ok(program) => program,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            program.start_profile();
+            println!("output: {:?}\n", program.run_to_halt());
+            print!("{}", program.take_profile().unwrap().report(20));
+        }
+        "coverage" => {
+            let input: Vec<i64> = args[1..]
+                .iter()
+                .map(|v| v.parse().expect("input must be integer"))
+                .collect();
+            let mut program = match intcode::Interpreter::parse(&read_program(), &input) {
+                Ok(program) => program,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return true;
+                }
+            };
+            program.start_coverage();
+            println!("output: {:?}\n", program.run_to_halt());
+            let coverage = program.take_coverage().unwrap();
+            print!("{}", coverage.listing(program.memory().dense_cells()));
+        }
+        "replay" => {
+            let session_file = args.get(1).expect("missing session file");
+            let session = intcode::Session::load(session_file).unwrap();
+            match session.replay(&read_program()) {
+                Ok(()) => println!("replayed {} events", session.events.len()),
+                Err(divergence) => println!("diverged at {}", divergence),
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
 fn main() {
-    let day = std::env::args()
-        .nth(1)
-        .expect("missing day argument")
-        .parse::<u64>()
-        .expect("day must be integer");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first().expect("missing day argument");
+    if run_intcode_tool(command, &args[1..]) {
+        return;
+    }
+
+    let day = command.parse::<u64>().expect("day must be integer");
 
     match day {
         1 => {